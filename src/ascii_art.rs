@@ -6,6 +6,9 @@ pub struct AsciiArt<'a> {
     bold: bool,
     start: usize,
     end: usize,
+    gradient: bool,
+    height: usize,
+    current_line: usize,
 }
 impl<'a> AsciiArt<'a> {
     pub fn new(input: &'a str, colors: Vec<Color>, bold: bool) -> AsciiArt<'a> {
@@ -30,17 +33,61 @@ impl<'a> AsciiArt<'a> {
             });
 
         AsciiArt {
+            height: lines.len(),
             content: Box::new(lines.into_iter()),
             colors,
             bold,
             start,
             end,
+            gradient: false,
+            current_line: 0,
         }
     }
+    /// Colors the whole logo with a vertical truecolor gradient going through
+    /// the colors instead of coloring each region with its own color
+    pub fn with_gradient(mut self, gradient: bool) -> Self {
+        self.gradient = gradient;
+        self
+    }
     pub fn width(&self) -> usize {
         assert!(self.end >= self.start);
         self.end - self.start
     }
+    /// Renders another logo to the right of this one, `gap` columns apart
+    pub fn beside(self, right: AsciiArt<'a>, gap: usize) -> SideBySide<'a> {
+        SideBySide {
+            left: self,
+            right,
+            gap,
+        }
+    }
+}
+
+/// Two logos rendered side by side, the shorter one padded with blank lines
+pub struct SideBySide<'a> {
+    left: AsciiArt<'a>,
+    right: AsciiArt<'a>,
+    gap: usize,
+}
+impl<'a> SideBySide<'a> {
+    pub fn width(&self) -> usize {
+        self.left.width() + self.gap + self.right.width()
+    }
+}
+
+impl<'a> Iterator for SideBySide<'a> {
+    type Item = String;
+    fn next(&mut self) -> Option<String> {
+        match (self.left.next(), self.right.next()) {
+            (None, None) => None,
+            (left, right) => Some(format!(
+                "{}{}{}",
+                left.unwrap_or_else(|| " ".repeat(self.left.width())),
+                " ".repeat(self.gap),
+                right.unwrap_or_else(|| " ".repeat(self.right.width()))
+            )),
+        }
+    }
 }
 
 /// Produces a series of lines which have been automatically truncated to the
@@ -48,9 +95,15 @@ impl<'a> AsciiArt<'a> {
 impl<'a> Iterator for AsciiArt<'a> {
     type Item = String;
     fn next(&mut self) -> Option<String> {
-        self.content
-            .next()
-            .map(|line| Tokens(line).render(&self.colors, self.start, self.end, self.bold))
+        let line = self.content.next()?;
+        let rendered = if self.gradient {
+            let color = gradient_color(&self.colors, self.current_line, self.height);
+            Tokens(line).render(&[color; 10], self.start, self.end, self.bold)
+        } else {
+            Tokens(line).render(&self.colors, self.start, self.end, self.bold)
+        };
+        self.current_line += 1;
+        Some(rendered)
     }
 }
 
@@ -198,6 +251,48 @@ fn succeed_when<I>(predicate: impl FnOnce(I) -> bool) -> impl FnOnce(I) -> Optio
     }
 }
 
+/// Interpolates the color of a line between the two closest colors of the palette
+fn gradient_color(palette: &[Color], line: usize, height: usize) -> Color {
+    if palette.len() < 2 || height < 2 {
+        return palette.first().copied().unwrap_or(Color::White);
+    }
+
+    let position = line as f32 / (height - 1) as f32 * (palette.len() - 1) as f32;
+    let index = (position.floor() as usize).min(palette.len() - 2);
+    let ratio = position - index as f32;
+    let (from, to) = (to_rgb(palette[index]), to_rgb(palette[index + 1]));
+    let mix = |a: u8, b: u8| (f32::from(a) + (f32::from(b) - f32::from(a)) * ratio).round() as u8;
+
+    Color::TrueColor {
+        r: mix(from.0, to.0),
+        g: mix(from.1, to.1),
+        b: mix(from.2, to.2),
+    }
+}
+
+/// RGB values of the terminal colors, as rendered by xterm
+pub fn to_rgb(color: Color) -> (u8, u8, u8) {
+    match color {
+        Color::Black => (0, 0, 0),
+        Color::Red => (205, 0, 0),
+        Color::Green => (0, 205, 0),
+        Color::Yellow => (205, 205, 0),
+        Color::Blue => (0, 0, 238),
+        Color::Magenta => (205, 0, 205),
+        Color::Cyan => (0, 205, 205),
+        Color::White => (229, 229, 229),
+        Color::BrightBlack => (127, 127, 127),
+        Color::BrightRed => (255, 0, 0),
+        Color::BrightGreen => (0, 255, 0),
+        Color::BrightYellow => (255, 255, 0),
+        Color::BrightBlue => (92, 92, 255),
+        Color::BrightMagenta => (255, 0, 255),
+        Color::BrightCyan => (0, 255, 255),
+        Color::BrightWhite => (255, 255, 255),
+        Color::TrueColor { r, g, b } => (r, g, b),
+    }
+}
+
 fn add_colored_segment(base: &mut String, segment: &str, color: Color, bold: bool) {
     let mut colored_segment = segment.color(color);
     if bold {
@@ -240,6 +335,17 @@ fn char_token(s: &str) -> ParseResult<Token> {
 mod test {
     use super::*;
 
+    #[test]
+    fn side_by_side_pads_the_shorter_logo() {
+        colored::control::SHOULD_COLORIZE.set_override(false);
+
+        let left = AsciiArt::new("ab\ncd\nef", Vec::new(), false);
+        let right = AsciiArt::new("x\ny", Vec::new(), false);
+        let logo = left.beside(right, 2);
+        assert_eq!(logo.width(), 5);
+        assert_eq!(logo.collect::<Vec<_>>(), vec!["ab  x", "cd  y", "ef   "]);
+    }
+
     #[test]
     fn space_parses() {
         assert_eq!(space_token(" "), Some(("", Token::Space)));
@@ -320,6 +426,33 @@ mod test {
         );
     }
 
+    #[test]
+    fn gradient() {
+        let palette = [Color::Black, Color::BrightWhite];
+
+        assert_eq!(
+            gradient_color(&palette, 0, 3),
+            Color::TrueColor { r: 0, g: 0, b: 0 }
+        );
+        assert_eq!(
+            gradient_color(&palette, 1, 3),
+            Color::TrueColor {
+                r: 128,
+                g: 128,
+                b: 128
+            }
+        );
+        assert_eq!(
+            gradient_color(&palette, 2, 3),
+            Color::TrueColor {
+                r: 255,
+                g: 255,
+                b: 255
+            }
+        );
+        assert_eq!(gradient_color(&[Color::Red], 1, 3), Color::Red);
+    }
+
     #[test]
     fn truncate() {
         assert_eq!(
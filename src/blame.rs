@@ -0,0 +1,54 @@
+use {
+    git2::{BlameOptions, Repository},
+    std::{collections::HashMap, path::PathBuf},
+};
+
+/// Counts, per author resolved through the mailmap, the lines of the source files they were
+/// the last to modify.
+/// Files which aren't recognized as source code by tokei are skipped. Blaming walks the history
+/// of every file, which takes a while on repositories with many files or commits.
+pub fn get_lines_owned_by_author(repo: &Repository, files: &[PathBuf]) -> HashMap<String, usize> {
+    let mut lines_owned = HashMap::new();
+    let workdir = match repo.workdir() {
+        Some(workdir) => workdir,
+        None => return lines_owned,
+    };
+    let tokei_config = tokei::Config::default();
+
+    for file in files {
+        if tokei::LanguageType::from_path(workdir.join(file), &tokei_config).is_none() {
+            continue;
+        }
+        let blame = match repo.blame_file(file, Some(BlameOptions::new().use_mailmap(true))) {
+            Ok(blame) => blame,
+            Err(_) => continue,
+        };
+        for hunk in blame.iter() {
+            let author = hunk
+                .final_signature()
+                .name()
+                .unwrap_or_default()
+                .to_string();
+            *lines_owned.entry(author).or_insert(0) += hunk.lines_in_hunk();
+        }
+    }
+
+    lines_owned
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, crate::fixture, std::fs};
+
+    #[test]
+    fn lines_are_owned_by_their_last_author() {
+        let (dir, repo) = fixture::repo(&[("main.rs", "fn main() {\n}\n")]);
+        fixture::commit(&repo, "alice", "first");
+        fs::write(dir.path().join("main.rs"), "fn main() {\n    run();\n}\n").unwrap();
+        fixture::commit(&repo, "bob", "second");
+
+        let lines_owned = get_lines_owned_by_author(&repo, &[PathBuf::from("main.rs")]);
+        assert_eq!(lines_owned.get("alice"), Some(&2));
+        assert_eq!(lines_owned.get("bob"), Some(&1));
+    }
+}
@@ -0,0 +1,83 @@
+use crate::{cli::BorderStyle, info::display_width};
+
+/// Corners, in the top-left, top-right, bottom-left and bottom-right order, then the horizontal
+/// and vertical lines of a box
+struct BoxChars {
+    corners: [char; 4],
+    horizontal: char,
+    vertical: char,
+}
+
+impl BorderStyle {
+    fn chars(self) -> BoxChars {
+        match self {
+            BorderStyle::Ascii => BoxChars {
+                corners: ['+', '+', '+', '+'],
+                horizontal: '-',
+                vertical: '|',
+            },
+            BorderStyle::Rounded => BoxChars {
+                corners: ['╭', '╮', '╰', '╯'],
+                horizontal: '─',
+                vertical: '│',
+            },
+            BorderStyle::Double => BoxChars {
+                corners: ['╔', '╗', '╚', '╝'],
+                horizontal: '═',
+                vertical: '║',
+            },
+        }
+    }
+}
+
+/// Draws a box around the rendered output, as wide as its widest line once the ANSI escape
+/// sequences are left out. The trailing blank lines stay below the box.
+pub fn draw(output: &str, style: BorderStyle) -> String {
+    let content = output.trim_end_matches('\n');
+    let trailing_newlines = output.len() - content.len();
+    let lines: Vec<&str> = content.lines().collect();
+    let width = lines
+        .iter()
+        .map(|line| display_width(line))
+        .max()
+        .unwrap_or(0);
+    let chars = style.chars();
+    let horizontal = chars.horizontal.to_string().repeat(width + 2);
+
+    let mut boxed = format!("{}{}{}\n", chars.corners[0], horizontal, chars.corners[1]);
+    for line in lines {
+        // A color left open by the line would bleed into the padding and the border
+        let reset = if line.contains('\x1b') { "\x1b[0m" } else { "" };
+        boxed.push_str(&format!(
+            "{} {}{}{} {}\n",
+            chars.vertical,
+            line,
+            reset,
+            " ".repeat(width - display_width(line)),
+            chars.vertical
+        ));
+    }
+    boxed.push_str(&format!(
+        "{}{}{}",
+        chars.corners[2], horizontal, chars.corners[3]
+    ));
+    boxed.push_str(&"\n".repeat(trailing_newlines.max(1)));
+    boxed
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn borders_align_on_the_display_width() {
+        let output = "\u{1b}[1;37mHEAD: \u{1b}[0mabc\nLanguages: 日本\n\n\n";
+        assert_eq!(
+            draw(output, BorderStyle::Ascii),
+            "+-----------------+\n\
+             | \u{1b}[1;37mHEAD: \u{1b}[0mabc\u{1b}[0m       |\n\
+             | Languages: 日本 |\n\
+             +-----------------+\n\n\n"
+        );
+    }
+}
@@ -0,0 +1,1643 @@
+use {
+    crate::{
+        extra_field::ExtraField,
+        image_backends,
+        info_field::{self, InfoFieldOn, InfoFields},
+        language::Language,
+        theme::Theme,
+        Error, Result,
+    },
+    clap::{App, Arg},
+    colored::*,
+    image::DynamicImage,
+    image_backends::ImageBackend,
+    std::{
+        ffi::OsString,
+        path::{Path, PathBuf},
+        str::FromStr,
+    },
+    strum::{EnumCount, EnumString, IntoEnumIterator},
+};
+
+/// Vendored and generated directories excluded unless `--no-auto-exclude` is passed
+pub const AUTO_EXCLUDED: [&str; 10] = [
+    "node_modules",
+    "vendor",
+    "target",
+    "dist",
+    "build",
+    ".venv",
+    "venv",
+    "__pycache__",
+    "bower_components",
+    "Pods",
+];
+
+/// Subdirectories searched for license files, following the REUSE `LICENSES/` convention
+pub const LICENSE_DIRECTORIES: [&str; 2] = ["LICENSES", "licenses"];
+
+/// Single value printed instead of the info with `--output`
+#[derive(PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum Output {
+    DominantExtension,
+    GitVersion,
+}
+
+/// Metric used to rank the authors
+#[derive(PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum AuthorsBy {
+    Commits,
+    Additions,
+    LinesOwned,
+}
+
+/// Refs pointing at HEAD shown next to its hash
+#[derive(PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum HeadRefs {
+    None,
+    Branches,
+    Tags,
+    All,
+}
+
+/// Order of the languages list
+#[derive(PartialEq, Eq, Clone, Copy, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum LanguagesSort {
+    Percent,
+    Name,
+}
+
+/// Lines of the box drawn around the output with `--border`
+#[derive(PartialEq, Eq, Clone, Copy, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum BorderStyle {
+    Ascii,
+    Rounded,
+    Double,
+}
+
+/// Layout of the info: the logo beside the fields, or a JSON object of their values
+#[derive(PartialEq, Eq, Clone, Copy, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum OutputFormat {
+    Ascii,
+    Json,
+}
+
+/// Detail of the size field
+#[derive(PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum SizeMode {
+    Packed,
+    Detailed,
+}
+
+pub struct Cli {
+    pub path: String,
+    pub ascii_language: Language,
+    pub ascii_colors: Vec<String>,
+    pub disabled_fields: InfoFieldOn,
+    pub bold: bool,
+    pub image: Option<DynamicImage>,
+    pub image_backend: Option<Box<dyn ImageBackend>>,
+    pub no_merges: bool,
+    pub no_color_blocks: bool,
+    pub number_of_authors: usize,
+    pub excluded: Vec<String>,
+    pub print_languages: bool,
+    pub monorepo: bool,
+    pub timezones: bool,
+    pub compare: Option<String>,
+    pub align_languages: bool,
+    pub logo_only: bool,
+    pub commit_style: bool,
+    pub gradient: bool,
+    pub extensions: bool,
+    pub authors_by: AuthorsBy,
+    pub placeholder: String,
+    pub commit_clock: bool,
+    pub build_system: bool,
+    pub human_numbers: bool,
+    pub tests: bool,
+    pub qr: bool,
+    pub languages_threshold: f64,
+    pub docker: bool,
+    pub velocity: bool,
+    pub name: Option<String>,
+    pub export: Option<PathBuf>,
+    pub co_authors: bool,
+    pub count_co_authors: bool,
+    pub theme: Theme,
+    pub committed_only: bool,
+    pub fetch_stats: bool,
+    pub trim: bool,
+    pub recent: bool,
+    pub padding: usize,
+    pub auto_exclude: bool,
+    pub verbose: bool,
+    pub commits_by_year: bool,
+    pub max_years: usize,
+    pub submodules: bool,
+    pub output: Option<Output>,
+    pub unicode: bool,
+    pub min_author_percent: f64,
+    pub no_git: bool,
+    pub docs: bool,
+    pub quiet: bool,
+    pub size: SizeMode,
+    pub active_this_month: bool,
+    pub exit_dirty: bool,
+    pub exit_no_license: bool,
+    pub dual_logo: Option<f64>,
+    pub unmerged_branches: bool,
+    pub percent_precision: Option<usize>,
+    pub ci: bool,
+    pub print_dimensions: bool,
+    pub dominant_min_files: usize,
+    pub dominant_min_lines: usize,
+    pub file_ages: bool,
+    pub watch: bool,
+    pub watch_worktree: bool,
+    pub license_dirs: Vec<String>,
+    pub head_signer: bool,
+    pub repo_url: Option<String>,
+    pub depth: Option<usize>,
+    pub ownership: bool,
+    pub weekend_stats: bool,
+    pub deploy: bool,
+    pub path_field: bool,
+    pub limit_commits: Option<usize>,
+    pub pager: bool,
+    pub indentation: bool,
+    pub head_refs: HeadRefs,
+    pub max_head_refs: Option<usize>,
+    pub direct_dep_licenses: bool,
+    pub languages_sort: LanguagesSort,
+    pub mine: bool,
+    pub merge_ratio: bool,
+    pub border: Option<BorderStyle>,
+    pub releases: bool,
+    pub releases_window: usize,
+    pub prefer_remote: Option<String>,
+    pub test_breakdown: bool,
+    pub commits_all: bool,
+    pub style_tools: bool,
+    pub redact: bool,
+    pub reading_time: bool,
+    pub reading_speed: usize,
+    pub extra_fields: Vec<ExtraField>,
+    pub tracked_share: bool,
+    pub fetch_count: bool,
+    pub branch_health: bool,
+    pub format: OutputFormat,
+    pub recent_days: usize,
+    pub loc_by_language: bool,
+    pub cache: bool,
+    pub number_of_languages: usize,
+}
+
+impl Cli {
+    /// Build `Cli` from command line arguments.
+    pub fn new() -> Result<Self> {
+        Cli::from_args(std::env::args_os())
+    }
+
+    /// Build `Cli` from the given arguments, the first one being the program name
+    pub fn from_args<I, T>(args: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let possible_languages: Vec<String> = Language::iter()
+            .filter(|language| *language != Language::Unknown)
+            .map(|language| language.to_string().to_lowercase())
+            .collect();
+
+        let possible_fields: Vec<&str> = InfoFields::iter()
+            .take(InfoFields::COUNT - 1)
+            .map(|field| field.into())
+            .collect();
+
+        #[cfg(target_os = "linux")]
+        let possible_backends = ["kitty", "sixel"];
+        #[cfg(not(target_os = "linux"))]
+        let possible_backends = [];
+
+        let matches = App::new(crate_name!())
+            .version(crate_version!())
+            .author("o2sh <ossama-hjaji@live.fr>")
+            .about(crate_description!())
+            .arg(Arg::with_name("input").default_value(".").help(
+                "Run as if onefetch was started in <input> instead of the current working directory.",
+            ))
+            .arg(
+                Arg::with_name("ascii-language")
+                    .short("a")
+                    .long("ascii-language")
+                    .takes_value(true)
+                    .possible_values(
+                        &possible_languages
+                            .iter()
+                            .map(|l| l.as_str())
+                            .collect::<Vec<&str>>(),
+                    )
+                    .case_insensitive(true)
+                    .help("Which language's ascii art to print."),
+            )
+            .arg(
+                Arg::with_name("disable-fields")
+                    .long("disable-fields")
+                    .short("d")
+                    .multiple(true)
+                    .takes_value(true)
+                    .case_insensitive(true)
+                    .help("Allows you to disable an info line from appearing in the output.")
+                    .possible_values(&possible_fields),
+            )
+            .arg(
+                Arg::with_name("only")
+                    .long("only")
+                    .takes_value(true)
+                    .use_delimiter(true)
+                    .case_insensitive(true)
+                    .conflicts_with("except")
+                    .help("Comma separated list of the only info lines to show.")
+                    .possible_values(&possible_fields),
+            )
+            .arg(
+                Arg::with_name("except")
+                    .long("except")
+                    .takes_value(true)
+                    .use_delimiter(true)
+                    .case_insensitive(true)
+                    .help("Comma separated list of info lines to hide.")
+                    .possible_values(&possible_fields),
+            )
+            .arg(
+                Arg::with_name("ascii-colors")
+                    .short("c")
+                    .long("ascii-colors")
+                    .multiple(true)
+                    .takes_value(true)
+                    .possible_values(&[
+                        "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12", "13",
+                        "14", "15",
+                    ])
+                    .hide_possible_values(true)
+                    .help(&format!(
+                        "Colors to print the ascii art. Possible values: [{0}{1}{2}{3}{4}{5}{6}{7}]",
+                        "0".black(),
+                        "1".red(),
+                        "2".green(),
+                        "3".yellow(),
+                        "4".blue(),
+                        "5".magenta(),
+                        "6".cyan(),
+                        "7".white()
+                    )),
+            )
+            .arg(
+                Arg::with_name("no-bold")
+                    .long("no-bold")
+                    .help("Turns off bold formatting."),
+            )
+            .arg(
+                Arg::with_name("languages")
+                    .short("l")
+                    .long("languages")
+                    .help("Prints out supported languages"),
+            )
+            .arg(
+                Arg::with_name("image")
+                    .short("i")
+                    .long("image")
+                    .takes_value(true)
+                    .help("Which image to use. Possible values: [/path/to/img]"),
+            )
+            .arg(
+                Arg::with_name("image-backend")
+                    .long("image-backend")
+                    .takes_value(true)
+                    .possible_values(&possible_backends)
+                    .help("Which image backend to use."),
+            )
+            .arg(
+                Arg::with_name("no-merge-commits")
+                    .long("no-merge-commits")
+                    .help("Ignores merge commits"),
+            )
+            .arg(
+                Arg::with_name("no-color-blocks")
+                    .long("no-color-blocks")
+                    .help("Hides the color blocks"),
+            )
+            .arg(
+                Arg::with_name("authors-number")
+                    .short("A")
+                    .long("authors-number")
+                    .takes_value(true)
+                    .default_value("3")
+                    .help("Number of authors to be shown."),
+            )
+            .arg(
+                Arg::with_name("exclude")
+                    .short("e")
+                    .long("exclude")
+                    .multiple(true)
+                    .takes_value(true)
+                    .help("Ignore all files & directories matching the pattern."),
+            )
+            .arg(
+                Arg::with_name("exclude-from")
+                    .long("exclude-from")
+                    .value_name("FILE")
+                    .takes_value(true)
+                    .help("Ignore all files & directories matching the patterns listed in FILE, one per line."),
+            )
+            .arg(
+                Arg::with_name("monorepo")
+                    .long("monorepo")
+                    .help("Shows the dominant language and size of each package in a monorepo."),
+            )
+            .arg(
+                Arg::with_name("timezones")
+                    .long("timezones")
+                    .help("Shows the UTC offsets the commits were authored from."),
+            )
+            .arg(
+                Arg::with_name("compare")
+                    .long("compare")
+                    .takes_value(true)
+                    .value_name("OTHER")
+                    .help("Compares the key metrics with those of the repository at <OTHER>."),
+            )
+            .arg(
+                Arg::with_name("align-languages")
+                    .long("align-languages")
+                    .help("Aligns the names and percentages of the languages in columns."),
+            )
+            .arg(
+                Arg::with_name("logo-only")
+                    .long("logo-only")
+                    .help("Prints only the ascii logo of the dominant language."),
+            )
+            .arg(
+                Arg::with_name("commit-style")
+                    .long("commit-style")
+                    .help("Shows the share of conventional commits and the average summary length."),
+            )
+            .arg(
+                Arg::with_name("gradient")
+                    .long("gradient")
+                    .help("Colors the ascii art with a gradient (requires a truecolor terminal)."),
+            )
+            .arg(
+                Arg::with_name("extensions")
+                    .long("extensions")
+                    .help("Shows the number of distinct file extensions and the most common ones."),
+            )
+            .arg(
+                Arg::with_name("authors-by")
+                    .long("authors-by")
+                    .takes_value(true)
+                    .possible_values(&["commits", "additions", "lines-owned"])
+                    .default_value("commits")
+                    .help("Ranks the authors by number of commits, lines added or lines currently owned according to git blame."),
+            )
+            .arg(
+                Arg::with_name("placeholder")
+                    .long("placeholder")
+                    .takes_value(true)
+                    .default_value("??")
+                    .help("Text shown in place of the values which could not be retrieved."),
+            )
+            .arg(
+                Arg::with_name("commit-clock")
+                    .long("commit-clock")
+                    .help("Shows when the commits are made, per day of the week and hour of the day."),
+            )
+            .arg(
+                Arg::with_name("build-system")
+                    .long("build-system")
+                    .help("Shows the build systems detected at the root of the repository."),
+            )
+            .arg(
+                Arg::with_name("human-numbers")
+                    .long("human-numbers")
+                    .value_name("BOOL")
+                    .takes_value(true)
+                    .possible_values(&["true", "false"])
+                    .default_value("true")
+                    .help("Separates the thousands of the commit count, lines of code and file count."),
+            )
+            .arg(
+                Arg::with_name("tests")
+                    .long("tests")
+                    .help("Shows the percentage of files which are tests."),
+            )
+            .arg(
+                Arg::with_name("qr")
+                    .long("qr")
+                    .help("Shows a QR code of the repository URL below the info."),
+            )
+            .arg(
+                Arg::with_name("languages-threshold")
+                    .long("languages-threshold")
+                    .value_name("PERCENT")
+                    .takes_value(true)
+                    .validator(|value| {
+                        f64::from_str(&value)
+                            .map(|_| ())
+                            .map_err(|_| String::from("must be a percentage"))
+                    })
+                    .help("Collapses the languages below PERCENT into \"Other\"."),
+            )
+            .arg(
+                Arg::with_name("docker")
+                    .long("docker")
+                    .help("Shows the base image of the Dockerfile at the root of the repository."),
+            )
+            .arg(
+                Arg::with_name("velocity")
+                    .long("velocity")
+                    .help("Shows the lines changed per month over the age of the repository."),
+            )
+            .arg(
+                Arg::with_name("name")
+                    .long("name")
+                    .value_name("NAME")
+                    .takes_value(true)
+                    .help("Overrides the project name."),
+            )
+            .arg(
+                Arg::with_name("export")
+                    .long("export")
+                    .value_name("FILE")
+                    .takes_value(true)
+                    .conflicts_with("image")
+                    .help("Writes the output with its colors to an SVG or PNG file instead of the terminal."),
+            )
+            .arg(
+                Arg::with_name("co-authors")
+                    .long("co-authors")
+                    .help("Shows the number of commits with Co-authored-by trailers and of distinct co-authors."),
+            )
+            .arg(
+                Arg::with_name("count-co-authors")
+                    .long("count-co-authors")
+                    .help("Credits co-authors with the commits they co-authored when ranking the authors by commits."),
+            )
+            .arg(
+                Arg::with_name("theme-file")
+                    .long("theme-file")
+                    .value_name("FILE")
+                    .takes_value(true)
+                    .help("Loads the colors, separator and boldness from a TOML theme file."),
+            )
+            .arg(
+                Arg::with_name("committed-only")
+                    .long("committed-only")
+                    .help("Counts the lines of code of the files committed in HEAD instead of the working tree."),
+            )
+            .arg(
+                Arg::with_name("fetch-stats")
+                    .long("fetch-stats")
+                    .help("Fetches the stars and forks of GitHub and GitLab projects from their API with curl, which must be installed."),
+            )
+            .arg(
+                Arg::with_name("trim")
+                    .long("trim")
+                    .help("Ends the output with a single newline instead of blank lines."),
+            )
+            .arg(
+                Arg::with_name("recent")
+                    .long("recent")
+                    .help("Shows a file changed by the most recent commit."),
+            )
+            .arg(
+                Arg::with_name("padding")
+                    .long("padding")
+                    .value_name("N")
+                    .takes_value(true)
+                    .default_value("3")
+                    .validator(|value| {
+                        usize::from_str(&value)
+                            .map(|_| ())
+                            .map_err(|_| String::from("must be a non-negative number"))
+                    })
+                    .help("Number of spaces between the logo and the info."),
+            )
+            .arg(
+                Arg::with_name("no-auto-exclude")
+                    .long("no-auto-exclude")
+                    .help("Scans the vendored and generated directories (node_modules, target, ...) too."),
+            )
+            .arg(
+                Arg::with_name("verbose")
+                    .long("verbose")
+                    .help("Prints the automatically excluded patterns."),
+            )
+            .arg(
+                Arg::with_name("commits-by-year")
+                    .long("commits-by-year")
+                    .help("Shows the number of commits of each year."),
+            )
+            .arg(
+                Arg::with_name("max-years")
+                    .long("max-years")
+                    .value_name("N")
+                    .takes_value(true)
+                    .default_value("5")
+                    .validator(|value| {
+                        usize::from_str(&value)
+                            .map(|_| ())
+                            .map_err(|_| String::from("must be a non-negative number"))
+                    })
+                    .help("Number of most recent years shown by --commits-by-year."),
+            )
+            .arg(
+                Arg::with_name("submodules")
+                    .long("submodules")
+                    .help("Shows the number of submodules and how many are out of sync."),
+            )
+            .arg(
+                Arg::with_name("output")
+                    .long("output")
+                    .value_name("VALUE")
+                    .takes_value(true)
+                    .possible_values(&["dominant-extension", "git-version"])
+                    .help("Prints only the given value, e.g. the primary file extension of the dominant language or the version of git."),
+            )
+            .arg(
+                Arg::with_name("ascii-only")
+                    .long("ascii-only")
+                    .conflicts_with("unicode")
+                    .help("Draws the charts and arrows with ASCII characters only."),
+            )
+            .arg(
+                Arg::with_name("unicode")
+                    .long("unicode")
+                    .help("Draws the charts and arrows with Unicode characters, whatever the locale."),
+            )
+            .arg(
+                Arg::with_name("min-author-percent")
+                    .long("min-author-percent")
+                    .value_name("PERCENT")
+                    .takes_value(true)
+                    .validator(|value| {
+                        f64::from_str(&value)
+                            .map(|_| ())
+                            .map_err(|_| String::from("must be a percentage"))
+                    })
+                    .help("Hides the authors with less than PERCENT of the commits, among the top --authors-number ones. The percentages stay relative to all the contributors."),
+            )
+            .arg(
+                Arg::with_name("no-git")
+                    .long("no-git")
+                    .help("Treats <input> as a plain directory, only showing the languages, lines of code, size and license."),
+            )
+            .arg(
+                Arg::with_name("docs")
+                    .long("docs")
+                    .help("Shows the percentage of files which are documentation."),
+            )
+            .arg(
+                Arg::with_name("quiet")
+                    .long("quiet")
+                    .short("q")
+                    .help("Hides the warnings."),
+            )
+            .arg(
+                Arg::with_name("size")
+                    .long("size")
+                    .takes_value(true)
+                    .possible_values(&["packed", "detailed"])
+                    .default_value("packed")
+                    .help("Shows the size of the packed objects only, or along with the sizes of the .git directory and the working tree."),
+            )
+            .arg(
+                Arg::with_name("active-this-month")
+                    .long("active-this-month")
+                    .help("Shows the number of distinct authors with a commit in the last 30 days."),
+            )
+            .arg(
+                Arg::with_name("exit-dirty")
+                    .long("exit-dirty")
+                    .help("Exits with code 2 when the working tree has pending changes, still printing the info."),
+            )
+            .arg(
+                Arg::with_name("exit-no-license")
+                    .long("exit-no-license")
+                    .help("Exits with code 4 when no license is detected, still printing the info. Combined with --exit-dirty, the codes add up."),
+            )
+            .arg(
+                Arg::with_name("dual-logo")
+                    .long("dual-logo")
+                    .help("Shows the logos of the two main languages side by side when their shares are within --dual-logo-margin."),
+            )
+            .arg(
+                Arg::with_name("dual-logo-margin")
+                    .long("dual-logo-margin")
+                    .value_name("PERCENT")
+                    .takes_value(true)
+                    .default_value("10")
+                    .validator(|value| {
+                        f64::from_str(&value)
+                            .map(|_| ())
+                            .map_err(|_| String::from("must be a percentage"))
+                    })
+                    .help("Largest difference between the shares of the two main languages for --dual-logo."),
+            )
+            .arg(
+                Arg::with_name("unmerged-branches")
+                    .long("unmerged-branches")
+                    .help("Shows the number of local branches not merged into the default branch."),
+            )
+            .arg(
+                Arg::with_name("percent-precision")
+                    .long("percent-precision")
+                    .value_name("N")
+                    .takes_value(true)
+                    .validator(|value| {
+                        usize::from_str(&value)
+                            .map(|_| ())
+                            .map_err(|_| String::from("must be a number of decimals"))
+                    })
+                    .help("Number of decimals of the language and author percentages, 1 and 0 by default."),
+            )
+            .arg(
+                Arg::with_name("ci")
+                    .long("ci")
+                    .help("Shows the CI systems configured at the root of the repository."),
+            )
+            .arg(
+                Arg::with_name("print-dimensions")
+                    .long("print-dimensions")
+                    .help("Prints the width and height of the output, as COLUMNSxROWS, to stderr."),
+            )
+            .arg(
+                Arg::with_name("dominant-min-files")
+                    .long("dominant-min-files")
+                    .value_name("N")
+                    .takes_value(true)
+                    .default_value("0")
+                    .validator(|value| {
+                        usize::from_str(&value)
+                            .map(|_| ())
+                            .map_err(|_| String::from("must be a number of files"))
+                    })
+                    .help("Minimum number of files of a language for its logo to be shown."),
+            )
+            .arg(
+                Arg::with_name("dominant-min-lines")
+                    .long("dominant-min-lines")
+                    .value_name("N")
+                    .takes_value(true)
+                    .default_value("0")
+                    .validator(|value| {
+                        usize::from_str(&value)
+                            .map(|_| ())
+                            .map_err(|_| String::from("must be a number of lines"))
+                    })
+                    .help("Minimum number of lines of code of a language for its logo to be shown."),
+            )
+            .arg(
+                Arg::with_name("file-ages")
+                    .long("file-ages")
+                    .help("Shows the files which were added the earliest and the latest."),
+            )
+            .arg(
+                Arg::with_name("watch")
+                    .long("watch")
+                    .help("Renders the info again whenever the repository changes, until Ctrl-C."),
+            )
+            .arg(
+                Arg::with_name("watch-worktree")
+                    .long("watch-worktree")
+                    .help("Same as --watch, also watching the changes to the working tree."),
+            )
+            .arg(
+                Arg::with_name("license-dir")
+                    .long("license-dir")
+                    .value_name("DIR")
+                    .multiple(true)
+                    .takes_value(true)
+                    .number_of_values(1)
+                    .help("Searches the license files of DIR as well as LICENSES/ (can be given multiple times)."),
+            )
+            .arg(
+                Arg::with_name("signer")
+                    .long("signer")
+                    .help("Shows the key which signed HEAD, or unsigned."),
+            )
+            .arg(
+                Arg::with_name("repo-url")
+                    .long("repo-url")
+                    .value_name("URL")
+                    .takes_value(true)
+                    .conflicts_with("watch")
+                    .conflicts_with("watch-worktree")
+                    .help("Clones the repository at URL into a temporary directory with git, which must be installed, and runs on the clone."),
+            )
+            .arg(
+                Arg::with_name("depth")
+                    .long("depth")
+                    .value_name("N")
+                    .takes_value(true)
+                    .default_value("1")
+                    .validator(|value| {
+                        usize::from_str(&value)
+                            .map(|_| ())
+                            .map_err(|_| String::from("must be a number of commits"))
+                    })
+                    .help("Number of commits cloned with --repo-url, 0 for the whole history."),
+            )
+            .arg(
+                Arg::with_name("ownership")
+                    .long("ownership")
+                    .help("Shows the lines of the source files owned by the top authors according to git blame (slow on large repositories)."),
+            )
+            .arg(
+                Arg::with_name("weekend-stats")
+                    .long("weekend-stats")
+                    .help("Shows the share of commits made on a Saturday or Sunday, in the committer's time zone."),
+            )
+            .arg(
+                Arg::with_name("deploy")
+                    .long("deploy")
+                    .help("Shows the deploy targets configured at the root of the repository."),
+            )
+            .arg(
+                Arg::with_name("path-field")
+                    .long("path-field")
+                    .help("Shows the absolute path of the repository."),
+            )
+            .arg(
+                Arg::with_name("limit-commits")
+                    .long("limit-commits")
+                    .value_name("N")
+                    .takes_value(true)
+                    .validator(|value| match usize::from_str(&value) {
+                        Ok(limit) if limit > 0 => Ok(()),
+                        _ => Err(String::from("must be a positive number of commits")),
+                    })
+                    .help("Only walks the N most recent commits, the commit count then being a lower bound."),
+            )
+            .arg(
+                Arg::with_name("pager")
+                    .long("pager")
+                    .help("Shows the output through $PAGER, or less -R, when stdout is a terminal."),
+            )
+            .arg(
+                Arg::with_name("no-pager")
+                    .long("no-pager")
+                    .help("Prints the output directly, overriding --pager."),
+            )
+            .arg(
+                Arg::with_name("indentation")
+                    .long("indentation")
+                    .help("Shows the most used indentation, sampled from the beginning of the source files."),
+            )
+            .arg(
+                Arg::with_name("head-refs")
+                    .long("head-refs")
+                    .takes_value(true)
+                    .possible_values(&["none", "branches", "tags", "all"])
+                    .default_value("all")
+                    .help("Which of the branches and tags pointing at HEAD to show next to it."),
+            )
+            .arg(
+                Arg::with_name("max-head-refs")
+                    .long("max-head-refs")
+                    .value_name("N")
+                    .takes_value(true)
+                    .validator(|value| {
+                        usize::from_str(&value)
+                            .map(|_| ())
+                            .map_err(|_| String::from("must be a number of refs"))
+                    })
+                    .help("Maximum number of refs shown next to HEAD, the others being counted."),
+            )
+            .arg(
+                Arg::with_name("direct-dep-licenses")
+                    .long("direct-dep-licenses")
+                    .help("Shows the licenses of the direct Cargo and npm dependencies, read from the local cargo registry and node_modules."),
+            )
+            .arg(
+                Arg::with_name("languages-sort")
+                    .long("languages-sort")
+                    .takes_value(true)
+                    .possible_values(&["percent", "name"])
+                    .default_value("percent")
+                    .help("Order of the languages list, Other always coming last."),
+            )
+            .arg(
+                Arg::with_name("mine")
+                    .long("mine")
+                    .help("Counts only the commits authored by the user.name or user.email of the git config."),
+            )
+            .arg(
+                Arg::with_name("merge-ratio")
+                    .long("merge-ratio")
+                    .help("Shows the share of merge commits in the history of the default branch."),
+            )
+            .arg(
+                Arg::with_name("border")
+                    .long("border")
+                    .value_name("STYLE")
+                    .takes_value(true)
+                    .min_values(0)
+                    .require_equals(true)
+                    .possible_values(&["ascii", "rounded", "double"])
+                    .conflicts_with("image")
+                    .help("Draws a box around the output, rounded by default and ascii with --ascii-only. A style is given as --border=STYLE."),
+            )
+            .arg(
+                Arg::with_name("releases")
+                    .long("releases")
+                    .help("Shows the number of tags created within the --releases-window."),
+            )
+            .arg(
+                Arg::with_name("releases-window")
+                    .long("releases-window")
+                    .value_name("DAYS")
+                    .takes_value(true)
+                    .default_value("365")
+                    .validator(|value| {
+                        usize::from_str(&value)
+                            .ok()
+                            .filter(|days| *days > 0)
+                            .map(|_| ())
+                            .ok_or_else(|| String::from("must be a positive number of days"))
+                    })
+                    .help("Number of days counted back by --releases."),
+            )
+            .arg(
+                Arg::with_name("prefer-remote")
+                    .long("prefer-remote")
+                    .value_name("REMOTE")
+                    .takes_value(true)
+                    .help("Remote whose URL gives the project name and URL, overriding the onefetch.canonicalRemote git config and falling back to origin."),
+            )
+            .arg(
+                Arg::with_name("test-breakdown")
+                    .long("test-breakdown")
+                    .help("Shows the share of the lines of each language which are in test files."),
+            )
+            .arg(
+                Arg::with_name("commits-all")
+                    .long("commits-all")
+                    .help("Also counts the distinct commits reachable from any branch or tag, next to those of the current branch."),
+            )
+            .arg(
+                Arg::with_name("style-tools")
+                    .long("style-tools")
+                    .help("Shows the formatters and style tools configured at the root of the repository."),
+            )
+            .arg(
+                Arg::with_name("redact")
+                    .long("redact")
+                    .help("Hides the owner and credentials of the repository URL, keeping its host and name, and leaves out the path field."),
+            )
+            .arg(
+                Arg::with_name("reading-time")
+                    .long("reading-time")
+                    .help("Shows an estimate of the time needed to read the documentation files."),
+            )
+            .arg(
+                Arg::with_name("reading-speed")
+                    .long("reading-speed")
+                    .value_name("WPM")
+                    .takes_value(true)
+                    .default_value("200")
+                    .validator(|value| {
+                        usize::from_str(&value)
+                            .ok()
+                            .filter(|words| *words > 0)
+                            .map(|_| ())
+                            .ok_or_else(|| String::from("must be a positive number of words per minute"))
+                    })
+                    .help("Words read per minute by --reading-time."),
+            )
+            .arg(
+                Arg::with_name("extra-field")
+                    .long("extra-field")
+                    .value_name("LABEL:COMMAND")
+                    .multiple(true)
+                    .takes_value(true)
+                    .number_of_values(1)
+                    .validator(|value| ExtraField::from_str(&value).map(|_| ()))
+                    .help("Shows the first line printed by COMMAND as a field named LABEL (can be given multiple times).")
+                    .long_help(
+                        "Shows the first line printed by COMMAND as a field named LABEL (can be given multiple times). \
+                         COMMAND runs through the shell in the repository directory, with your privileges, \
+                         and shows the placeholder if it fails or takes more than 5 seconds. \
+                         Only pass commands you trust: they run every time onefetch does, on any repository."
+                    ),
+            )
+            .arg(
+                Arg::with_name("tracked-share")
+                    .long("tracked-share")
+                    .help("Shows the percentage of the files of the working tree which are tracked, leaving out the ignored ones."),
+            )
+            .arg(
+                Arg::with_name("fetch-count")
+                    .long("fetch-count")
+                    .help("Counts the commits of a shallow clone on its remote, which needs network access; falls back to the local count, a lower bound."),
+            )
+            .arg(
+                Arg::with_name("branch-health")
+                    .long("branch-health")
+                    .help("Shows whether the history of the default branch is linear, signed and conventional."),
+            )
+            .arg(
+                Arg::with_name("format")
+                    .long("format")
+                    .takes_value(true)
+                    .possible_values(&["ascii", "json"])
+                    .default_value("ascii")
+                    .help("Prints the info beside the logo, or as a JSON object without the logo and the color blocks."),
+            )
+            .arg(
+                Arg::with_name("recent-days")
+                    .long("recent-days")
+                    .value_name("DAYS")
+                    .takes_value(true)
+                    .default_value("7")
+                    .validator(|value| {
+                        usize::from_str(&value)
+                            .ok()
+                            .filter(|days| *days > 0)
+                            .map(|_| ())
+                            .ok_or_else(|| String::from("must be a positive number of days"))
+                    })
+                    .help("Number of days counted back by the recent activity field."),
+            )
+            .arg(
+                Arg::with_name("loc-by-language")
+                    .long("loc-by-language")
+                    .help("Shows the lines of code of each language along with its percentage."),
+            )
+            .arg(
+                Arg::with_name("separator-char")
+                    .long("separator-char")
+                    .value_name("CHAR")
+                    .takes_value(true)
+                    .validator(|value| match value.chars().count() {
+                        1 => Ok(()),
+                        _ => Err(String::from("must be a single character")),
+                    })
+                    .help("Character of the line under the git user and version, - by default."),
+            )
+            .arg(
+                Arg::with_name("cache")
+                    .long("cache")
+                    .help("Reuses the language stats of the previous run while HEAD and the working tree are unchanged, caching them in the git directory."),
+            )
+            .arg(
+                Arg::with_name("number-of-languages")
+                    .long("number-of-languages")
+                    .value_name("NUM")
+                    .takes_value(true)
+                    .default_value("6")
+                    .validator(|value| {
+                        if value == "all" {
+                            return Ok(());
+                        }
+                        usize::from_str(&value)
+                            .ok()
+                            .filter(|number| *number > 0)
+                            .map(|_| ())
+                            .ok_or_else(|| String::from("must be a positive number or all"))
+                    })
+                    .help("Number of languages listed before the rest is collapsed into Other, all listing every language."),
+            )
+            .get_matches_from(args);
+
+        let mut excluded: Vec<String> = if let Some(user_ignored) = matches.values_of("exclude") {
+            user_ignored.map(String::from).collect()
+        } else {
+            Vec::new()
+        };
+
+        let auto_exclude = !matches.is_present("no-auto-exclude");
+        if auto_exclude {
+            excluded.extend(AUTO_EXCLUDED.iter().map(|pattern| pattern.to_string()));
+        }
+
+        if let Some(exclude_file) = matches.value_of("exclude-from") {
+            let content =
+                std::fs::read_to_string(exclude_file).map_err(|_| Error::ReadExcludeFile)?;
+            excluded.extend(parse_exclude_file(&content));
+        }
+
+        let mut license_dirs: Vec<String> = LICENSE_DIRECTORIES
+            .iter()
+            .map(|dir| dir.to_string())
+            .collect();
+        if let Some(values) = matches.values_of("license-dir") {
+            license_dirs.extend(values.map(String::from));
+        }
+
+        let print_languages = matches.is_present("languages");
+
+        let path = String::from(matches.value_of("input").unwrap());
+
+        let ascii_language = if let Some(ascii_language) = matches.value_of("ascii-language") {
+            Language::from_str(&ascii_language.to_lowercase()).unwrap()
+        } else {
+            Language::Unknown
+        };
+
+        let mut fields_to_hide: Vec<String> =
+            if let Some(values) = matches.values_of("disable-fields") {
+                values.map(String::from).collect()
+            } else {
+                Vec::new()
+            };
+
+        if let Some(values) = matches.values_of("except") {
+            fields_to_hide.extend(values.map(String::from));
+        }
+
+        if let Some(values) = matches.values_of("only") {
+            let fields_to_show: Vec<String> = values.map(|v| v.to_lowercase()).collect();
+            fields_to_hide.extend(
+                possible_fields
+                    .iter()
+                    .filter(|field| !fields_to_show.iter().any(|shown| shown == *field))
+                    .map(|field| field.to_string()),
+            );
+        }
+
+        let disabled_fields = info_field::get_disabled_fields(fields_to_hide);
+
+        let ascii_colors: Vec<String> = if let Some(values) = matches.values_of("ascii-colors") {
+            values.map(String::from).collect()
+        } else {
+            Vec::new()
+        };
+
+        let mut theme = match matches.value_of("theme-file") {
+            Some(path) => Theme::from_file(Path::new(path))?,
+            None => Theme::default(),
+        };
+        if let Some(separator) = matches.value_of("separator-char") {
+            theme.separator = separator.chars().next();
+        }
+
+        let bold = !matches.is_present("no-bold") && theme.bold.unwrap_or(true);
+
+        let mut image = if let Some(image_path) = matches.value_of("image") {
+            Some(image_backends::load_image(Path::new(image_path))?)
+        } else {
+            None
+        };
+
+        let image_backend = if image.is_some() {
+            if let Some(backend_name) = matches.value_of("image-backend") {
+                #[cfg(target_os = "linux")]
+                let backend = Some(match backend_name {
+                    "kitty" => Box::new(image_backends::kitty::KittyBackend::new())
+                        as Box<dyn ImageBackend>,
+                    "sixel" => Box::new(image_backends::sixel::SixelBackend::new())
+                        as Box<dyn ImageBackend>,
+                    _ => unreachable!(),
+                });
+                #[cfg(not(target_os = "linux"))]
+                let backend = None;
+                backend
+            } else {
+                image_backends::get_best_backend()
+            }
+        } else {
+            None
+        };
+        if image.is_some() && image_backend.is_none() {
+            eprintln!(
+                "Warning: no image backend is supported by this terminal, showing the ascii logo"
+            );
+            image = None;
+        }
+
+        let no_merges = matches.is_present("no-merge-commits");
+
+        let no_color_blocks = matches.is_present("no-color-blocks");
+
+        let number_of_authors: usize = if let Some(value) = matches.value_of("authors-number") {
+            usize::from_str(value).unwrap()
+        } else {
+            3
+        };
+
+        let monorepo = matches.is_present("monorepo");
+
+        let timezones = matches.is_present("timezones");
+
+        let compare = matches.value_of("compare").map(String::from);
+
+        let align_languages = matches.is_present("align-languages");
+
+        let logo_only = matches.is_present("logo-only");
+
+        let commit_style = matches.is_present("commit-style");
+
+        let gradient = matches.is_present("gradient");
+
+        let extensions = matches.is_present("extensions");
+
+        let commit_clock = matches.is_present("commit-clock");
+
+        let build_system = matches.is_present("build-system");
+
+        let human_numbers = matches.value_of("human-numbers") == Some("true");
+
+        let tests = matches.is_present("tests");
+
+        let qr = matches.is_present("qr");
+
+        let docker = matches.is_present("docker");
+
+        let velocity = matches.is_present("velocity");
+
+        let name = matches.value_of("name").map(String::from);
+
+        let export = matches.value_of("export").map(PathBuf::from);
+
+        let co_authors = matches.is_present("co-authors");
+
+        let count_co_authors = matches.is_present("count-co-authors");
+
+        let committed_only = matches.is_present("committed-only");
+
+        let fetch_stats = matches.is_present("fetch-stats");
+
+        let trim = matches.is_present("trim");
+
+        let recent = matches.is_present("recent");
+
+        let verbose = matches.is_present("verbose");
+
+        let commits_by_year = matches.is_present("commits-by-year");
+
+        let submodules = matches.is_present("submodules");
+
+        let unicode = if matches.is_present("ascii-only") {
+            false
+        } else if matches.is_present("unicode") {
+            true
+        } else {
+            terminal_supports_unicode()
+        };
+
+        let output = matches
+            .value_of("output")
+            .map(|output| Output::from_str(output).unwrap());
+
+        let max_years = usize::from_str(matches.value_of("max-years").unwrap()).unwrap();
+
+        let padding = usize::from_str(matches.value_of("padding").unwrap()).unwrap();
+
+        let languages_threshold = matches
+            .value_of("languages-threshold")
+            .map_or(0.0, |value| f64::from_str(value).unwrap());
+
+        let no_git = matches.is_present("no-git");
+
+        let docs = matches.is_present("docs");
+
+        let quiet = matches.is_present("quiet");
+
+        let active_this_month = matches.is_present("active-this-month");
+
+        let dual_logo = if matches.is_present("dual-logo") {
+            Some(f64::from_str(matches.value_of("dual-logo-margin").unwrap()).unwrap())
+        } else {
+            None
+        };
+
+        let unmerged_branches = matches.is_present("unmerged-branches");
+
+        let ci = matches.is_present("ci");
+
+        let print_dimensions = matches.is_present("print-dimensions");
+
+        let file_ages = matches.is_present("file-ages");
+
+        let head_signer = matches.is_present("signer");
+
+        let ownership = matches.is_present("ownership");
+
+        let weekend_stats = matches.is_present("weekend-stats");
+
+        let deploy = matches.is_present("deploy");
+
+        let path_field = matches.is_present("path-field");
+
+        let indentation = matches.is_present("indentation");
+
+        let languages_sort =
+            LanguagesSort::from_str(matches.value_of("languages-sort").unwrap()).unwrap();
+
+        let direct_dep_licenses = matches.is_present("direct-dep-licenses");
+
+        let mine = matches.is_present("mine");
+
+        let merge_ratio = matches.is_present("merge-ratio");
+
+        let releases = matches.is_present("releases");
+
+        let releases_window =
+            usize::from_str(matches.value_of("releases-window").unwrap()).unwrap();
+
+        let prefer_remote = matches.value_of("prefer-remote").map(String::from);
+
+        let test_breakdown = matches.is_present("test-breakdown");
+
+        let commits_all = matches.is_present("commits-all");
+
+        let style_tools = matches.is_present("style-tools");
+
+        let redact = matches.is_present("redact");
+
+        let reading_time = matches.is_present("reading-time");
+
+        let reading_speed = usize::from_str(matches.value_of("reading-speed").unwrap()).unwrap();
+
+        let extra_fields: Vec<ExtraField> = matches
+            .values_of("extra-field")
+            .map(|values| values.map(|value| value.parse().unwrap()).collect())
+            .unwrap_or_default();
+
+        let tracked_share = matches.is_present("tracked-share");
+
+        let fetch_count = matches.is_present("fetch-count");
+
+        let branch_health = matches.is_present("branch-health");
+
+        let format = OutputFormat::from_str(matches.value_of("format").unwrap()).unwrap();
+
+        let recent_days = usize::from_str(matches.value_of("recent-days").unwrap()).unwrap();
+
+        let loc_by_language = matches.is_present("loc-by-language");
+
+        let cache = matches.is_present("cache");
+
+        let number_of_languages = match matches.value_of("number-of-languages").unwrap() {
+            "all" => usize::MAX,
+            number => usize::from_str(number).unwrap(),
+        };
+
+        let border = if !matches.is_present("border") {
+            None
+        } else if !unicode {
+            Some(BorderStyle::Ascii)
+        } else {
+            Some(
+                matches
+                    .value_of("border")
+                    .map_or(BorderStyle::Rounded, |style| {
+                        BorderStyle::from_str(style).unwrap()
+                    }),
+            )
+        };
+
+        let head_refs = HeadRefs::from_str(matches.value_of("head-refs").unwrap()).unwrap();
+
+        let max_head_refs = matches
+            .value_of("max-head-refs")
+            .map(|value| usize::from_str(value).unwrap());
+
+        let pager = matches.is_present("pager") && !matches.is_present("no-pager");
+
+        let limit_commits = matches
+            .value_of("limit-commits")
+            .map(|value| usize::from_str(value).unwrap());
+
+        let repo_url = matches.value_of("repo-url").map(String::from);
+
+        let depth = Some(usize::from_str(matches.value_of("depth").unwrap()).unwrap())
+            .filter(|&depth| depth > 0);
+
+        let watch_worktree = matches.is_present("watch-worktree");
+
+        let watch = matches.is_present("watch") || watch_worktree;
+
+        let dominant_min_files =
+            usize::from_str(matches.value_of("dominant-min-files").unwrap()).unwrap();
+
+        let dominant_min_lines =
+            usize::from_str(matches.value_of("dominant-min-lines").unwrap()).unwrap();
+
+        let percent_precision = matches
+            .value_of("percent-precision")
+            .map(|value| usize::from_str(value).unwrap());
+
+        let exit_dirty = matches.is_present("exit-dirty");
+
+        let exit_no_license = matches.is_present("exit-no-license");
+
+        let size = SizeMode::from_str(matches.value_of("size").unwrap()).unwrap();
+
+        let min_author_percent = matches
+            .value_of("min-author-percent")
+            .map_or(0.0, |value| f64::from_str(value).unwrap());
+
+        let placeholder = String::from(matches.value_of("placeholder").unwrap());
+
+        let authors_by = AuthorsBy::from_str(matches.value_of("authors-by").unwrap()).unwrap();
+
+        Ok(Cli {
+            path,
+            ascii_language,
+            ascii_colors,
+            disabled_fields,
+            bold,
+            image,
+            image_backend,
+            no_merges,
+            no_color_blocks,
+            number_of_authors,
+            excluded,
+            print_languages,
+            monorepo,
+            timezones,
+            compare,
+            align_languages,
+            logo_only,
+            commit_style,
+            gradient,
+            extensions,
+            authors_by,
+            placeholder,
+            commit_clock,
+            build_system,
+            human_numbers,
+            tests,
+            qr,
+            languages_threshold,
+            docker,
+            velocity,
+            name,
+            export,
+            co_authors,
+            count_co_authors,
+            theme,
+            committed_only,
+            fetch_stats,
+            trim,
+            recent,
+            padding,
+            auto_exclude,
+            verbose,
+            commits_by_year,
+            max_years,
+            submodules,
+            output,
+            unicode,
+            min_author_percent,
+            no_git,
+            docs,
+            quiet,
+            size,
+            active_this_month,
+            exit_dirty,
+            exit_no_license,
+            dual_logo,
+            unmerged_branches,
+            percent_precision,
+            ci,
+            print_dimensions,
+            dominant_min_files,
+            dominant_min_lines,
+            file_ages,
+            watch,
+            watch_worktree,
+            license_dirs,
+            head_signer,
+            repo_url,
+            depth,
+            ownership,
+            weekend_stats,
+            deploy,
+            path_field,
+            limit_commits,
+            pager,
+            indentation,
+            head_refs,
+            max_head_refs,
+            direct_dep_licenses,
+            languages_sort,
+            mine,
+            merge_ratio,
+            border,
+            releases,
+            releases_window,
+            prefer_remote,
+            test_breakdown,
+            commits_all,
+            style_tools,
+            redact,
+            reading_time,
+            reading_speed,
+            extra_fields,
+            tracked_share,
+            fetch_count,
+            branch_health,
+            format,
+            recent_days,
+            loc_by_language,
+            cache,
+            number_of_languages,
+        })
+    }
+}
+
+impl Default for Cli {
+    fn default() -> Self {
+        Cli {
+            path: String::from("."),
+            ascii_language: Language::Unknown,
+            ascii_colors: Vec::new(),
+            disabled_fields: InfoFieldOn::default(),
+            bold: true,
+            image: None,
+            image_backend: None,
+            no_merges: false,
+            no_color_blocks: false,
+            number_of_authors: 3,
+            excluded: AUTO_EXCLUDED
+                .iter()
+                .map(|pattern| pattern.to_string())
+                .collect(),
+            print_languages: false,
+            monorepo: false,
+            timezones: false,
+            compare: None,
+            align_languages: false,
+            logo_only: false,
+            commit_style: false,
+            gradient: false,
+            extensions: false,
+            authors_by: AuthorsBy::Commits,
+            placeholder: String::from("??"),
+            commit_clock: false,
+            build_system: false,
+            human_numbers: true,
+            tests: false,
+            qr: false,
+            languages_threshold: 0.0,
+            docker: false,
+            velocity: false,
+            name: None,
+            export: None,
+            co_authors: false,
+            count_co_authors: false,
+            theme: Theme::default(),
+            committed_only: false,
+            fetch_stats: false,
+            trim: false,
+            recent: false,
+            padding: 3,
+            auto_exclude: true,
+            verbose: false,
+            commits_by_year: false,
+            max_years: 5,
+            submodules: false,
+            output: None,
+            unicode: true,
+            min_author_percent: 0.0,
+            no_git: false,
+            docs: false,
+            quiet: false,
+            size: SizeMode::Packed,
+            active_this_month: false,
+            exit_dirty: false,
+            exit_no_license: false,
+            dual_logo: None,
+            unmerged_branches: false,
+            percent_precision: None,
+            ci: false,
+            print_dimensions: false,
+            dominant_min_files: 0,
+            dominant_min_lines: 0,
+            file_ages: false,
+            watch: false,
+            watch_worktree: false,
+            license_dirs: LICENSE_DIRECTORIES
+                .iter()
+                .map(|dir| dir.to_string())
+                .collect(),
+            head_signer: false,
+            repo_url: None,
+            depth: Some(1),
+            ownership: false,
+            weekend_stats: false,
+            deploy: false,
+            path_field: false,
+            limit_commits: None,
+            pager: false,
+            indentation: false,
+            head_refs: HeadRefs::All,
+            max_head_refs: None,
+            direct_dep_licenses: false,
+            languages_sort: LanguagesSort::Percent,
+            mine: false,
+            merge_ratio: false,
+            border: None,
+            releases: false,
+            releases_window: 365,
+            prefer_remote: None,
+            test_breakdown: false,
+            commits_all: false,
+            style_tools: false,
+            redact: false,
+            reading_time: false,
+            reading_speed: 200,
+            extra_fields: Vec::new(),
+            tracked_share: false,
+            fetch_count: false,
+            branch_health: false,
+            format: OutputFormat::Ascii,
+            recent_days: 7,
+            loc_by_language: false,
+            cache: false,
+            number_of_languages: 6,
+        }
+    }
+}
+
+/// Whether the locale of the terminal, from `$LC_ALL`, `$LC_CTYPE` or `$LANG`, uses UTF-8
+#[cfg(not(windows))]
+fn terminal_supports_unicode() -> bool {
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty());
+    locale_supports_unicode(locale.as_deref())
+}
+
+#[cfg(windows)]
+fn terminal_supports_unicode() -> bool {
+    true
+}
+
+/// An unset locale falls back to the `C` locale, which is ASCII
+fn locale_supports_unicode(locale: Option<&str>) -> bool {
+    match locale {
+        Some(locale) => {
+            let locale = locale.to_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        }
+        None => false,
+    }
+}
+
+/// Returns the patterns of an exclude file, skipping blank lines and `#` comments
+fn parse_exclude_file(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn non_utf8_locales_fall_back_to_ascii() {
+        assert!(!locale_supports_unicode(None));
+        assert!(!locale_supports_unicode(Some("C")));
+        assert!(!locale_supports_unicode(Some("POSIX")));
+        assert!(!locale_supports_unicode(Some("en_US.ISO-8859-1")));
+        assert!(locale_supports_unicode(Some("en_US.UTF-8")));
+        assert!(locale_supports_unicode(Some("C.utf8")));
+    }
+
+    #[test]
+    fn exclude_file_skips_comments_and_blank_lines() {
+        let content = "# generated code\ntarget\n\n  \nvendor/**  \n# fixtures\ntests/fixtures\n";
+        assert_eq!(
+            parse_exclude_file(content),
+            vec!["target", "vendor/**", "tests/fixtures"]
+        );
+    }
+
+    #[test]
+    fn border_without_a_style_leaves_the_path_alone() {
+        let config = Cli::from_args(["onefetch", "--border", "/tmp/repo"]).unwrap();
+        assert_eq!(config.path, "/tmp/repo");
+        assert!(config.border.is_some());
+
+        let config = Cli::from_args(["onefetch", "--border=ascii", "/tmp/repo"]).unwrap();
+        assert_eq!(config.path, "/tmp/repo");
+        assert!(config.border == Some(BorderStyle::Ascii));
+    }
+}
@@ -0,0 +1,137 @@
+use {
+    crate::{history::Identity, Error, Result},
+    git2::Oid,
+    std::{
+        fs,
+        path::{Path, PathBuf},
+        process::{Command, Stdio},
+        time::{SystemTime, UNIX_EPOCH},
+    },
+};
+
+/// Clone of a remote repository in a temporary directory, removed when dropped.
+///
+/// The clones are made by the git command line rather than by git2: libgit2 can't make shallow
+/// (`--depth`) or partial (`--filter`) clones, so cloning a large repository would fetch its
+/// whole history, and it doesn't go through the credential helpers and the ssh config of git.
+pub struct TempClone {
+    path: PathBuf,
+}
+
+impl TempClone {
+    /// Clones the repository at `url` with `git clone`, keeping the last `depth` commits when
+    /// given
+    pub fn new(url: &str, depth: Option<usize>) -> Result<Self> {
+        let args: Vec<String> = depth
+            .map(|depth| format!("--depth={}", depth))
+            .into_iter()
+            .collect();
+        TempClone::clone(url, &args)
+    }
+
+    /// Clones the commits of the repository at `url` into a bare repository, leaving out the
+    /// trees and blobs, which is all counting the commits needs
+    pub fn history_only(url: &str) -> Result<Self> {
+        TempClone::clone(
+            url,
+            &[
+                String::from("--bare"),
+                String::from("--filter=tree:0"),
+                String::from("--no-tags"),
+            ],
+        )
+    }
+
+    fn clone(url: &str, args: &[String]) -> Result<Self> {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.subsec_nanos())
+            .unwrap_or_default();
+        let path =
+            std::env::temp_dir().join(format!("onefetch-clone-{}-{}", std::process::id(), nanos));
+        let clone = TempClone { path };
+
+        let output = Command::new("git")
+            .args(["clone", "--quiet"])
+            .args(args)
+            .arg("--")
+            .arg(url)
+            .arg(&clone.path)
+            .stdin(Stdio::null())
+            // Fails instead of waiting for credentials nobody is there to type
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .output()
+            .map_err(|_| Error::GitNotInstalled)?;
+        if !output.status.success() {
+            let message = String::from_utf8_lossy(&output.stderr);
+            return Err(Error::Clone(message.trim().to_string()));
+        }
+
+        Ok(clone)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempClone {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Counts the commits reachable from `oid` on the repository at `url`, which a shallow clone
+/// can't do locally. Any failure (offline, unknown commit, ...) yields `None`.
+pub fn count_remote_commits(
+    url: &str,
+    oid: Oid,
+    no_merges: bool,
+    author: Option<&Identity>,
+) -> Option<usize> {
+    if url.is_empty() {
+        return None;
+    }
+    let clone = TempClone::history_only(url).ok()?;
+
+    let mut command = Command::new("git");
+    command
+        .arg("-C")
+        .arg(clone.path())
+        .args(["rev-list", "--count"]);
+    if no_merges {
+        command.arg("--no-merges");
+    }
+    if let Some(author) = author {
+        command
+            .arg("--fixed-strings")
+            .arg(format!("--author={}", author.author_pattern()));
+    }
+    let output = command.arg(oid.to_string()).output().ok()?;
+
+    if output.status.success() {
+        String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, crate::fixture, git2::Repository};
+
+    #[test]
+    fn remote_commits_are_counted_past_the_shallow_boundary() {
+        let (dir, repo) = fixture::repo(&[]);
+        fixture::commit(&repo, "onefetch", "first");
+        fixture::commit(&repo, "onefetch", "second");
+        let head = fixture::commit(&repo, "onefetch", "third");
+        let url = format!("file://{}", dir.path().display());
+
+        let shallow = TempClone::new(&url, Some(1)).unwrap();
+        assert!(Repository::open(shallow.path()).unwrap().is_shallow());
+        assert_eq!(count_remote_commits(&url, head, false, None), Some(3));
+        assert_eq!(count_remote_commits(&url, Oid::zero(), false, None), None);
+        assert_eq!(count_remote_commits("", head, false, None), None);
+    }
+}
@@ -3,27 +3,71 @@ use git2::Oid;
 pub struct CommitInfo {
     commit: Oid,
     refs: Vec<String>,
+    max_refs: Option<usize>,
 }
 
 impl CommitInfo {
-    pub fn new(commit: Oid, refs: Vec<String>) -> CommitInfo {
-        CommitInfo { commit, refs }
+    /// The refs beyond `max_refs` are only counted when displayed
+    pub fn new(commit: Oid, refs: Vec<String>, max_refs: Option<usize>) -> CommitInfo {
+        CommitInfo {
+            commit,
+            refs,
+            max_refs,
+        }
+    }
+
+    pub fn commit(&self) -> Oid {
+        self.commit
+    }
+
+    pub fn refs(&self) -> &[String] {
+        &self.refs
     }
 }
 
 impl std::fmt::Display for CommitInfo {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         let short_commit = self.commit.to_string().chars().take(7).collect::<String>();
-        if !self.refs.is_empty() {
-            let refs_str = self
-                .refs
+        let shown = self
+            .max_refs
+            .unwrap_or(self.refs.len())
+            .min(self.refs.len());
+        write!(f, "{}", short_commit)?;
+        if shown > 0 {
+            let refs_str = self.refs[..shown]
                 .iter()
                 .map(|ref_name| ref_name.as_str())
                 .collect::<Vec<&str>>()
                 .join(", ");
-            write!(f, "{} ({})", short_commit, refs_str)
-        } else {
-            write!(f, "{}", short_commit)
+            write!(f, " ({})", refs_str)?;
+        }
+        if shown < self.refs.len() {
+            write!(f, " (+{} more)", self.refs.len() - shown)?;
         }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn refs_beyond_the_maximum_are_counted() {
+        let refs = vec![
+            String::from("main"),
+            String::from("origin/main"),
+            String::from("tags/v1.0"),
+        ];
+        let commit = Oid::from_str("0123456789abcdef0123456789abcdef01234567").unwrap();
+
+        let info = CommitInfo::new(commit, refs.clone(), None);
+        assert_eq!(info.to_string(), "0123456 (main, origin/main, tags/v1.0)");
+
+        let info = CommitInfo::new(commit, refs.clone(), Some(1));
+        assert_eq!(info.to_string(), "0123456 (main) (+2 more)");
+
+        let info = CommitInfo::new(commit, refs, Some(0));
+        assert_eq!(info.to_string(), "0123456 (+3 more)");
     }
 }
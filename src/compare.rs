@@ -0,0 +1,94 @@
+use {
+    crate::info::Info,
+    colored::Colorize,
+    std::fmt::{self, Display, Write},
+};
+
+/// Side by side summary of the key metrics of two repositories
+pub struct Comparison<'a> {
+    left: &'a Info,
+    right: &'a Info,
+    arrow: &'static str,
+}
+
+impl<'a> Comparison<'a> {
+    pub fn new(left: &'a Info, right: &'a Info) -> Self {
+        let arrow = if left.unicode() { "→" } else { "->" };
+        Comparison { left, right, arrow }
+    }
+
+    fn write_delta(&self, buf: &mut String, label: &str, left: usize, right: usize) -> fmt::Result {
+        let delta = if right >= left {
+            format!("+{}", format_number(right - left))
+        } else {
+            format!("-{}", format_number(left - right))
+        };
+        writeln!(
+            buf,
+            "{}{} {} {} ({})",
+            label.bold(),
+            format_number(left),
+            self.arrow,
+            format_number(right),
+            delta
+        )
+    }
+}
+
+impl<'a> Display for Comparison<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut buf = String::new();
+
+        writeln!(
+            buf,
+            "{} {} {}",
+            display_name(self.left).bold(),
+            self.arrow.bold(),
+            display_name(self.right).bold()
+        )?;
+        self.write_delta(
+            &mut buf,
+            "Lines of code: ",
+            self.left.number_of_lines(),
+            self.right.number_of_lines(),
+        )?;
+        self.write_delta(
+            &mut buf,
+            "Commits: ",
+            self.left.number_of_commits(),
+            self.right.number_of_commits(),
+        )?;
+        writeln!(
+            buf,
+            "{}{} {} {}",
+            "Language: ".bold(),
+            self.left.dominant_language(),
+            self.arrow,
+            self.right.dominant_language()
+        )?;
+        self.write_delta(
+            &mut buf,
+            "Contributors: ",
+            self.left.number_of_contributors(),
+            self.right.number_of_contributors(),
+        )?;
+
+        write!(f, "{}", buf)
+    }
+}
+
+fn display_name(info: &Info) -> &str {
+    info.or_placeholder(Some(info.project_name()).filter(|name| !name.is_empty()))
+}
+
+/// Formats a number with `,` as the thousands separator
+pub fn format_number(number: usize) -> String {
+    number
+        .to_string()
+        .as_bytes()
+        .rchunks(3)
+        .rev()
+        .map(|digits| std::str::from_utf8(digits).unwrap())
+        .collect::<Vec<_>>()
+        .join(",")
+}
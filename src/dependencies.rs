@@ -0,0 +1,228 @@
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+/// Sections of a Cargo manifest listing the dependencies built into the package
+const CARGO_DEPENDENCY_TABLES: [&str; 2] = ["dependencies", "build-dependencies"];
+/// SPDX identifier prefixes of the licenses whose obligations extend to the dependents
+const COPYLEFT_LICENSES: [&str; 9] = [
+    "GPL", "LGPL", "AGPL", "MPL", "EPL", "EUPL", "CDDL", "OSL", "CC-BY-SA",
+];
+
+/// Declared licenses of the direct dependencies, the expressions being kept as written
+pub struct DependencyLicenses {
+    /// Each license with its number of dependencies, the most common first
+    pub licenses: Vec<(String, usize)>,
+    /// Number of dependencies whose license couldn't be found locally
+    pub unknown: usize,
+}
+
+/// Reads the direct dependencies of the Cargo and npm manifests at the root of the
+/// repository, and their licenses from the local cargo registry and `node_modules`.
+/// Returns `None` without any dependency.
+pub fn get_direct_dependency_licenses(workdir: &Path) -> Option<DependencyLicenses> {
+    let cargo_home = env::var_os("CARGO_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".cargo")));
+
+    let mut licenses: Vec<Option<String>> = cargo_licenses(workdir, cargo_home.as_deref());
+    licenses.extend(npm_licenses(workdir));
+    if licenses.is_empty() {
+        return None;
+    }
+
+    let unknown = licenses.iter().filter(|license| license.is_none()).count();
+    let mut counts = HashMap::new();
+    for license in licenses.into_iter().flatten() {
+        *counts.entry(license).or_insert(0) += 1;
+    }
+    let mut licenses: Vec<(String, usize)> = counts.into_iter().collect();
+    licenses.sort_by(|(a_license, a_count), (b_license, b_count)| {
+        b_count.cmp(a_count).then(a_license.cmp(b_license))
+    });
+
+    Some(DependencyLicenses { licenses, unknown })
+}
+
+/// Tells whether a license expression leaves no choice but to comply with a copyleft license
+pub fn is_copyleft(expression: &str) -> bool {
+    expression
+        .split(" OR ")
+        .flat_map(|alternative| alternative.split('/'))
+        .all(|alternative| {
+            alternative.split(" AND ").any(|license| {
+                let license = license.trim_matches(|c: char| c == '(' || c == ')' || c == ' ');
+                COPYLEFT_LICENSES
+                    .iter()
+                    .any(|copyleft| license.starts_with(copyleft))
+            })
+        })
+}
+
+fn cargo_licenses(workdir: &Path, cargo_home: Option<&Path>) -> Vec<Option<String>> {
+    let manifest = match read_toml(&workdir.join("Cargo.toml")) {
+        Some(manifest) => manifest,
+        None => return Vec::new(),
+    };
+    let locked_versions = locked_versions(workdir);
+    let sources: Vec<PathBuf> = cargo_home
+        .and_then(|cargo_home| fs::read_dir(cargo_home.join("registry").join("src")).ok())
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .collect();
+
+    let mut tables: Vec<&toml::Value> = CARGO_DEPENDENCY_TABLES
+        .iter()
+        .filter_map(|table| manifest.get(table))
+        .collect();
+    if let Some(targets) = manifest.get("target").and_then(toml::Value::as_table) {
+        for target in targets.values() {
+            tables.extend(
+                CARGO_DEPENDENCY_TABLES
+                    .iter()
+                    .filter_map(|table| target.get(table)),
+            );
+        }
+    }
+
+    tables
+        .into_iter()
+        .filter_map(toml::Value::as_table)
+        .flat_map(|table| table.iter())
+        .map(|(name, dependency)| {
+            // A path dependency declares its license in its own manifest
+            if let Some(path) = dependency.get("path").and_then(toml::Value::as_str) {
+                return cargo_package_license(&workdir.join(path));
+            }
+            let name = dependency
+                .get("package")
+                .and_then(toml::Value::as_str)
+                .unwrap_or(name);
+            let versions = locked_versions.get(name)?;
+            sources.iter().find_map(|source| {
+                versions.iter().find_map(|version| {
+                    cargo_package_license(&source.join(format!("{}-{}", name, version)))
+                })
+            })
+        })
+        .collect()
+}
+
+/// Returns the versions of each package of the Cargo.lock file
+fn locked_versions(workdir: &Path) -> HashMap<String, Vec<String>> {
+    let mut versions: HashMap<String, Vec<String>> = HashMap::new();
+    let lock_file = read_toml(&workdir.join("Cargo.lock"));
+    let packages = lock_file
+        .as_ref()
+        .and_then(|lock_file| lock_file.get("package"))
+        .and_then(toml::Value::as_array);
+    for package in packages.into_iter().flatten() {
+        let name = package.get("name").and_then(toml::Value::as_str);
+        let version = package.get("version").and_then(toml::Value::as_str);
+        if let (Some(name), Some(version)) = (name, version) {
+            versions
+                .entry(name.to_string())
+                .or_default()
+                .push(version.to_string());
+        }
+    }
+    versions
+}
+
+fn cargo_package_license(package_dir: &Path) -> Option<String> {
+    let manifest = read_toml(&package_dir.join("Cargo.toml"))?;
+    let license = manifest.get("package")?.get("license")?.as_str()?;
+    Some(license.to_string())
+}
+
+fn read_toml(path: &Path) -> Option<toml::Value> {
+    toml::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+fn npm_licenses(workdir: &Path) -> Vec<Option<String>> {
+    let manifest = match read_json(&workdir.join("package.json")) {
+        Some(manifest) => manifest,
+        None => return Vec::new(),
+    };
+    let dependencies = manifest
+        .get("dependencies")
+        .and_then(serde_json::Value::as_object);
+
+    dependencies
+        .into_iter()
+        .flat_map(|dependencies| dependencies.keys())
+        .map(|name| {
+            let package = read_json(&workdir.join("node_modules").join(name).join("package.json"))?;
+            match package.get("license")? {
+                serde_json::Value::String(license) => Some(license.clone()),
+                // Deprecated `{ "type": "MIT", "url": ... }` form
+                license => Some(license.get("type")?.as_str()?.to_string()),
+            }
+        })
+        .collect()
+}
+
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, crate::fixture};
+
+    #[test]
+    fn licenses_are_read_from_the_registry_and_node_modules() {
+        let dir = fixture::dir(&[
+            (
+                "Cargo.toml",
+                "[package]\nname = \"app\"\n[dependencies]\nserde = \"1\"\nmissing = \"1\"\n\
+                 [target.'cfg(unix)'.dependencies]\nfoo = { version = \"1\", package = \"gpl-foo\" }\n",
+            ),
+            (
+                "Cargo.lock",
+                "[[package]]\nname = \"serde\"\nversion = \"1.0.1\"\n\n\
+                 [[package]]\nname = \"gpl-foo\"\nversion = \"0.1.0\"\n",
+            ),
+            (
+                "cargo/registry/src/index/serde-1.0.1/Cargo.toml",
+                "[package]\nlicense = \"MIT OR Apache-2.0\"\n",
+            ),
+            (
+                "cargo/registry/src/index/gpl-foo-0.1.0/Cargo.toml",
+                "[package]\nlicense = \"GPL-3.0-only\"\n",
+            ),
+            ("package.json", r#"{"dependencies": {"left-pad": "^1.0.0"}}"#),
+            (
+                "node_modules/left-pad/package.json",
+                r#"{"license": "MIT OR Apache-2.0"}"#,
+            ),
+        ]);
+
+        let mut licenses = cargo_licenses(dir.path(), Some(&dir.path().join("cargo")));
+        licenses.extend(npm_licenses(dir.path()));
+        licenses.sort();
+        assert_eq!(
+            licenses,
+            vec![
+                None,
+                Some(String::from("GPL-3.0-only")),
+                Some(String::from("MIT OR Apache-2.0")),
+                Some(String::from("MIT OR Apache-2.0")),
+            ]
+        );
+    }
+
+    #[test]
+    fn copyleft_requires_every_alternative_to_be_copyleft() {
+        assert!(is_copyleft("GPL-3.0-only"));
+        assert!(is_copyleft("MPL-2.0"));
+        assert!(is_copyleft("LGPL-2.1 OR GPL-3.0"));
+        assert!(is_copyleft("MIT AND GPL-2.0"));
+        assert!(!is_copyleft("MIT OR GPL-3.0"));
+        assert!(!is_copyleft("MIT/Apache-2.0"));
+        assert!(!is_copyleft("Apache-2.0"));
+    }
+}
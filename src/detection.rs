@@ -0,0 +1,125 @@
+use std::{fs, path::Path};
+
+/// A tool along with the files or directories, relative to the root of the
+/// repository, whose presence reveals it
+type DetectionTable = [(&'static str, &'static [&'static str])];
+
+pub const BUILD_SYSTEMS: &DetectionTable = &[
+    ("Cargo", &["Cargo.toml"]),
+    ("CMake", &["CMakeLists.txt"]),
+    ("Make", &["Makefile", "makefile", "GNUmakefile"]),
+    ("Gradle", &["build.gradle", "build.gradle.kts"]),
+    ("Maven", &["pom.xml"]),
+    ("npm", &["package.json"]),
+    ("Meson", &["meson.build"]),
+    ("Bazel", &["BUILD.bazel", "WORKSPACE"]),
+];
+
+pub const CI_SYSTEMS: &DetectionTable = &[
+    ("GitHub Actions", &[".github/workflows"]),
+    ("GitLab CI", &[".gitlab-ci.yml"]),
+    ("CircleCI", &[".circleci/config.yml"]),
+    ("Travis CI", &[".travis.yml"]),
+    ("Jenkins", &["Jenkinsfile"]),
+];
+
+pub const DEPLOY_TARGETS: &DetectionTable = &[
+    ("Fly.io", &["fly.toml"]),
+    ("Heroku", &["Procfile"]),
+    ("Vercel", &["vercel.json"]),
+    ("Netlify", &["netlify.toml"]),
+    (
+        "Kubernetes",
+        &["k8s", "kubernetes", "kustomization.yaml", "Chart.yaml"],
+    ),
+    (
+        "Docker Compose",
+        &[
+            "docker-compose.yml",
+            "docker-compose.yaml",
+            "compose.yml",
+            "compose.yaml",
+        ],
+    ),
+];
+
+pub const STYLE_TOOLS: &DetectionTable = &[
+    ("EditorConfig", &[".editorconfig"]),
+    ("rustfmt", &["rustfmt.toml", ".rustfmt.toml"]),
+    (
+        "Prettier",
+        &[
+            ".prettierrc",
+            ".prettierrc.json",
+            ".prettierrc.json5",
+            ".prettierrc.yaml",
+            ".prettierrc.yml",
+            ".prettierrc.toml",
+            ".prettierrc.js",
+            ".prettierrc.cjs",
+            ".prettierrc.mjs",
+            "prettier.config.js",
+            "prettier.config.cjs",
+            "prettier.config.mjs",
+        ],
+    ),
+    ("clang-format", &[".clang-format", "_clang-format"]),
+];
+
+/// Tools configured by a `[tool.<key>]` table of `pyproject.toml`, along with their key
+pub const PYPROJECT_STYLE_TOOLS: [(&str, &str); 3] =
+    [("Black", "black"), ("Ruff", "ruff"), ("isort", "isort")];
+
+/// Returns the names of the tools of the table found at the root of the repository
+pub fn detect(workdir: &Path, table: &DetectionTable) -> Vec<&'static str> {
+    table
+        .iter()
+        .filter(|(_, paths)| paths.iter().any(|path| workdir.join(path).exists()))
+        .map(|(name, _)| *name)
+        .collect()
+}
+
+/// Returns the names of the tools configured in the `pyproject.toml` at the root of the repository
+pub fn detect_pyproject(workdir: &Path, tools: &[(&'static str, &str)]) -> Vec<&'static str> {
+    let pyproject = fs::read_to_string(workdir.join("pyproject.toml"))
+        .ok()
+        .and_then(|content| toml::from_str::<toml::Value>(&content).ok());
+    let tool = match pyproject
+        .as_ref()
+        .and_then(|pyproject| pyproject.get("tool"))
+    {
+        Some(tool) => tool,
+        None => return Vec::new(),
+    };
+    tools
+        .iter()
+        .filter(|(_, key)| tool.get(key).is_some())
+        .map(|(name, _)| *name)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, crate::fixture};
+
+    #[test]
+    fn style_tools_are_detected_from_files_and_pyproject() {
+        let dir = fixture::dir(&[
+            (".editorconfig", "root = true\n"),
+            (".prettierrc.json", "{}\n"),
+            (
+                "pyproject.toml",
+                "[tool.black]\nline-length = 100\n\n[tool.pytest.ini_options]\n",
+            ),
+        ]);
+
+        assert_eq!(
+            detect(dir.path(), STYLE_TOOLS),
+            vec!["EditorConfig", "Prettier"]
+        );
+        assert_eq!(
+            detect_pyproject(dir.path(), &PYPROJECT_STYLE_TOOLS),
+            vec!["Black"]
+        );
+    }
+}
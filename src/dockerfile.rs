@@ -0,0 +1,39 @@
+use std::{collections::HashMap, fs, path::Path};
+
+/// Returns the base image of the final stage of the Dockerfile at the root of the repository.
+/// A stage built `FROM` an earlier stage resolves to the base image of that stage.
+pub fn get_base_image(workdir: &Path) -> Option<String> {
+    let content = fs::read_to_string(workdir.join("Dockerfile")).ok()?;
+    parse_base_image(&content)
+}
+
+fn parse_base_image(content: &str) -> Option<String> {
+    let mut stages: HashMap<String, String> = HashMap::new();
+    let mut base_image = None;
+
+    for line in content.lines() {
+        let mut words = line.split_whitespace();
+        match words.next() {
+            Some(instruction) if instruction.eq_ignore_ascii_case("from") => {}
+            _ => continue,
+        }
+        let mut words = words.filter(|word| !word.starts_with("--"));
+        let image = match words.next() {
+            Some(image) => image,
+            None => continue,
+        };
+        let image = stages
+            .get(&image.to_lowercase())
+            .cloned()
+            .unwrap_or_else(|| image.to_string());
+
+        if let (Some(keyword), Some(name)) = (words.next(), words.next()) {
+            if keyword.eq_ignore_ascii_case("as") {
+                stages.insert(name.to_lowercase(), image.clone());
+            }
+        }
+        base_image = Some(image);
+    }
+
+    base_image
+}
@@ -14,14 +14,42 @@ pub enum Error {
     BareGitRepo,
     /// Repository is a bare git repo
     ReferenceInfoError,
-    /// Image probably doesn't exist or has wrong format
-    ImageLoadError,
     /// Could not initialize the license detector
     LicenseDetectorError,
+    /// Could not read the git index
+    ReadIndex,
+    /// Could not read the file passed to --exclude-from
+    ReadExcludeFile,
+    /// Could not write the file passed to --export
+    Export,
+    /// The file passed to --export has an extension other than .svg or .png
+    UnsupportedExportFormat,
+    /// The file passed to --theme-file couldn't be read or is invalid
+    ThemeFile(String),
+    /// Could not gather the info of one of the compared repositories
+    Compare(String, Box<Error>),
+    /// The repository passed to --repo-url couldn't be cloned
+    Clone(String),
+    /// The image passed to --image doesn't exist or couldn't be decoded
+    ImageDecode(String, String),
+    /// --mine was passed without user.name or user.email in the git config
+    NoGitIdentity,
 }
 
 impl std::fmt::Debug for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if let Error::Compare(path, error) = self {
+            return write!(f, "{}: {:?}", path, error);
+        }
+        if let Error::ThemeFile(message) = self {
+            return write!(f, "Invalid theme file: {}", message);
+        }
+        if let Error::ImageDecode(path, reason) = self {
+            return write!(f, "Could not load the image {}: {}", path, reason);
+        }
+        if let Error::Clone(message) = self {
+            return write!(f, "Could not clone the repository: {}", message);
+        }
         let content = match self {
             Error::SourceCodeNotFound => "Could not find any source code in this directory",
             Error::GitNotInstalled => "Git failed to execute",
@@ -30,8 +58,16 @@ impl std::fmt::Debug for Error {
             Error::NotGitRepo => "Could not find a valid git repo on the current path",
             Error::BareGitRepo => "Unable to run onefetch on bare git repos",
             Error::ReferenceInfoError => "Error while retrieving reference information",
-            Error::ImageLoadError => "Could not load the specified image",
             Error::LicenseDetectorError => "Could not initialize the license detector",
+            Error::ReadIndex => "Could not read the git index",
+            Error::ReadExcludeFile => "Could not read the exclude file",
+            Error::Export => "Could not write the exported file",
+            Error::UnsupportedExportFormat => "Only .svg and .png files can be exported",
+            Error::NoGitIdentity => "--mine needs user.name or user.email in the git config",
+            Error::Compare(..)
+            | Error::ThemeFile(..)
+            | Error::Clone(..)
+            | Error::ImageDecode(..) => unreachable!(),
         };
         write!(f, "{}", content)
     }
@@ -0,0 +1,347 @@
+use {
+    crate::{ascii_art, Error, Result},
+    colored::Color,
+    image::{Rgb, RgbImage},
+    std::{fmt::Write, fs, path::Path},
+};
+
+mod font;
+
+const FONT_SIZE: usize = 14;
+const LINE_HEIGHT: usize = 18;
+/// Advance of a monospace glyph, in pixels, at `FONT_SIZE`
+const CHAR_WIDTH: f64 = 8.4;
+const MARGIN: usize = 16;
+/// Pixels of the PNG export per pixel of the 8x8 glyphs
+const GLYPH_SCALE: u32 = 2;
+const CELL_WIDTH: u32 = 8 * GLYPH_SCALE;
+const CELL_HEIGHT: u32 = 10 * GLYPH_SCALE;
+const BACKGROUND: (u8, u8, u8) = (30, 30, 30);
+const FOREGROUND: (u8, u8, u8) = (229, 229, 229);
+
+const ANSI_COLORS: [Color; 8] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::White,
+];
+const ANSI_BRIGHT_COLORS: [Color; 8] = [
+    Color::BrightBlack,
+    Color::BrightRed,
+    Color::BrightGreen,
+    Color::BrightYellow,
+    Color::BrightBlue,
+    Color::BrightMagenta,
+    Color::BrightCyan,
+    Color::BrightWhite,
+];
+
+/// Writes the rendered output, ANSI colors included, to an SVG or PNG image.
+/// The SVG leaves the text to the monospace font of the viewer, the PNG draws it with a bundled
+/// 8x8 bitmap font covering ASCII.
+pub fn export(rendered: &str, path: &Path) -> Result<()> {
+    let extension = path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase());
+    match extension.as_deref() {
+        Some("svg") => fs::write(path, to_svg(rendered)).map_err(|_| Error::Export),
+        Some("png") => to_png(rendered).save(path).map_err(|_| Error::Export),
+        _ => Err(Error::UnsupportedExportFormat),
+    }
+}
+
+#[derive(Clone, Copy, Default)]
+struct Style {
+    foreground: Option<(u8, u8, u8)>,
+    background: Option<(u8, u8, u8)>,
+    bold: bool,
+}
+
+struct Span {
+    column: usize,
+    text: String,
+    style: Style,
+}
+
+fn to_svg(rendered: &str) -> String {
+    let lines: Vec<Vec<Span>> = rendered.trim_end().lines().map(parse_line).collect();
+    let columns = lines
+        .iter()
+        .filter_map(|spans| spans.last())
+        .map(|span| span.column + span.text.chars().count())
+        .max()
+        .unwrap_or(0);
+    let width = (columns as f64 * CHAR_WIDTH).ceil() as usize + 2 * MARGIN;
+    let height = lines.len() * LINE_HEIGHT + 2 * MARGIN;
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#,
+        width, height
+    );
+    let _ = writeln!(
+        svg,
+        r#"<rect width="100%" height="100%" fill="{}"/>"#,
+        hex(BACKGROUND)
+    );
+    let _ = writeln!(
+        svg,
+        r#"<g font-family="monospace" font-size="{}" xml:space="preserve">"#,
+        FONT_SIZE
+    );
+
+    for (row, spans) in lines.iter().enumerate() {
+        let top = MARGIN + row * LINE_HEIGHT;
+        for span in spans {
+            let x = MARGIN as f64 + span.column as f64 * CHAR_WIDTH;
+            if let Some(background) = span.style.background {
+                let _ = writeln!(
+                    svg,
+                    r#"<rect x="{:.1}" y="{}" width="{:.1}" height="{}" fill="{}"/>"#,
+                    x,
+                    top,
+                    span.text.chars().count() as f64 * CHAR_WIDTH,
+                    LINE_HEIGHT,
+                    hex(background)
+                );
+            }
+            if span.text.trim().is_empty() {
+                continue;
+            }
+            let _ = writeln!(
+                svg,
+                r#"<text x="{:.1}" y="{}" fill="{}"{}>{}</text>"#,
+                x,
+                top + FONT_SIZE,
+                hex(span.style.foreground.unwrap_or(FOREGROUND)),
+                if span.style.bold {
+                    r#" font-weight="bold""#
+                } else {
+                    ""
+                },
+                escape(&span.text)
+            );
+        }
+    }
+
+    svg.push_str("</g>\n</svg>\n");
+    svg
+}
+
+fn to_png(rendered: &str) -> RgbImage {
+    let lines: Vec<Vec<Span>> = rendered.trim_end().lines().map(parse_line).collect();
+    let columns = lines
+        .iter()
+        .filter_map(|spans| spans.last())
+        .map(|span| span.column + span.text.chars().count())
+        .max()
+        .unwrap_or(0);
+    let margin = MARGIN as u32;
+    let width = columns as u32 * CELL_WIDTH + 2 * margin;
+    let height = lines.len() as u32 * CELL_HEIGHT + 2 * margin;
+    let (r, g, b) = BACKGROUND;
+    let mut image = RgbImage::from_pixel(width, height, Rgb([r, g, b]));
+
+    for (row, spans) in lines.iter().enumerate() {
+        let top = margin + row as u32 * CELL_HEIGHT;
+        for span in spans {
+            for (i, c) in span.text.chars().enumerate() {
+                let left = margin + (span.column + i) as u32 * CELL_WIDTH;
+                if let Some((r, g, b)) = span.style.background {
+                    fill(
+                        &mut image,
+                        left,
+                        top,
+                        CELL_WIDTH,
+                        CELL_HEIGHT,
+                        Rgb([r, g, b]),
+                    );
+                }
+                let (r, g, b) = span.style.foreground.unwrap_or(FOREGROUND);
+                draw_char(&mut image, c, left, top, span.style.bold, Rgb([r, g, b]));
+            }
+        }
+    }
+
+    image
+}
+
+/// Draws the character in the cell whose top left corner is at `left`, `top`. Bold characters
+/// are drawn twice, one pixel apart.
+/// The lower block elements of the sparklines fill the bottom of the cell, the characters
+/// missing from the font are drawn as `?`.
+fn draw_char(image: &mut RgbImage, c: char, left: u32, top: u32, bold: bool, color: Rgb<u8>) {
+    if let '\u{2581}'..='\u{2588}' = c {
+        let height = CELL_HEIGHT * (c as u32 - 0x2580) / 8;
+        fill(
+            image,
+            left,
+            top + CELL_HEIGHT - height,
+            CELL_WIDTH,
+            height,
+            color,
+        );
+        return;
+    }
+
+    let index = (c as usize)
+        .checked_sub(font::FIRST as usize)
+        .filter(|&index| index < font::GLYPHS.len())
+        .unwrap_or('?' as usize - font::FIRST as usize);
+    let glyph_top = top + (CELL_HEIGHT - 8 * GLYPH_SCALE) / 2;
+    for (y, bits) in font::GLYPHS[index].iter().enumerate() {
+        for x in (0..8).filter(|x| bits >> x & 1 == 1) {
+            let x = left + x * GLYPH_SCALE;
+            let y = glyph_top + y as u32 * GLYPH_SCALE;
+            fill(image, x, y, GLYPH_SCALE, GLYPH_SCALE, color);
+            if bold {
+                fill(image, x + 1, y, GLYPH_SCALE, GLYPH_SCALE, color);
+            }
+        }
+    }
+}
+
+fn fill(image: &mut RgbImage, left: u32, top: u32, width: u32, height: u32, color: Rgb<u8>) {
+    for y in top..(top + height).min(image.height()) {
+        for x in left..(left + width).min(image.width()) {
+            image.put_pixel(x, y, color);
+        }
+    }
+}
+
+/// Splits a line into runs of text sharing the same SGR style
+fn parse_line(line: &str) -> Vec<Span> {
+    let mut spans = Vec::new();
+    let mut style = Style::default();
+    let mut column = 0;
+    let mut text = String::new();
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            text.push(c);
+            continue;
+        }
+        let mut sequence = String::new();
+        if chars.peek() == Some(&'[') {
+            chars.next();
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    sequence.push(c);
+                    break;
+                }
+                sequence.push(c);
+            }
+        }
+        if let Some(parameters) = sequence.strip_suffix('m') {
+            if !text.is_empty() {
+                let length = text.chars().count();
+                spans.push(Span {
+                    column,
+                    text: std::mem::take(&mut text),
+                    style,
+                });
+                column += length;
+            }
+            apply_sgr(&mut style, parameters);
+        }
+    }
+    if !text.is_empty() {
+        spans.push(Span {
+            column,
+            text,
+            style,
+        });
+    }
+    spans
+}
+
+fn apply_sgr(style: &mut Style, parameters: &str) {
+    let codes: Vec<u8> = parameters
+        .split(';')
+        .map(|code| code.parse().unwrap_or(0))
+        .collect();
+    let mut codes = codes.into_iter();
+
+    while let Some(code) = codes.next() {
+        match code {
+            0 => *style = Style::default(),
+            1 => style.bold = true,
+            22 => style.bold = false,
+            30..=37 => style.foreground = Some(ascii_art::to_rgb(ANSI_COLORS[code as usize - 30])),
+            39 => style.foreground = None,
+            40..=47 => style.background = Some(ascii_art::to_rgb(ANSI_COLORS[code as usize - 40])),
+            49 => style.background = None,
+            90..=97 => {
+                style.foreground = Some(ascii_art::to_rgb(ANSI_BRIGHT_COLORS[code as usize - 90]))
+            }
+            100..=107 => {
+                style.background = Some(ascii_art::to_rgb(ANSI_BRIGHT_COLORS[code as usize - 100]))
+            }
+            38 | 48 if codes.next() == Some(2) => {
+                let rgb = (
+                    codes.next().unwrap_or(0),
+                    codes.next().unwrap_or(0),
+                    codes.next().unwrap_or(0),
+                );
+                if code == 38 {
+                    style.foreground = Some(rgb);
+                } else {
+                    style.background = Some(rgb);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn hex((r, g, b): (u8, u8, u8)) -> String {
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn png_cells_are_drawn_in_their_colors() {
+        let image = to_png("\x1b[41m \x1b[0m|\n\x1b[32m\u{2588}");
+        assert_eq!(image.width(), 2 * CELL_WIDTH + 2 * MARGIN as u32);
+        assert_eq!(image.height(), 2 * CELL_HEIGHT + 2 * MARGIN as u32);
+
+        let margin = MARGIN as u32;
+        let red = ascii_art::to_rgb(Color::Red);
+        let green = ascii_art::to_rgb(Color::Green);
+        let pixel = |x: u32, y: u32| {
+            let Rgb([r, g, b]) = *image.get_pixel(x, y);
+            (r, g, b)
+        };
+        assert_eq!(pixel(0, 0), BACKGROUND);
+        assert_eq!(pixel(margin, margin), red);
+        assert_eq!(
+            pixel(margin + CELL_WIDTH - 1, margin + CELL_HEIGHT - 1),
+            red
+        );
+        // The bar of `|` is in the middle of the second cell
+        assert_eq!(
+            pixel(
+                margin + CELL_WIDTH + 3 * GLYPH_SCALE,
+                margin + CELL_HEIGHT / 2
+            ),
+            FOREGROUND
+        );
+        assert_eq!(pixel(margin + CELL_WIDTH, margin), BACKGROUND);
+        assert_eq!(pixel(margin, margin + CELL_HEIGHT), green);
+    }
+}
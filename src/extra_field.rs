@@ -0,0 +1,102 @@
+use {
+    futures::future::join_all,
+    std::{path::Path, process::Stdio, str::FromStr, time::Duration},
+    tokio::process::Command,
+};
+
+/// How long the command of an extra field may run before its field shows the placeholder
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A field computed by a command of the user, given as `Label:command` to `--extra-field`.
+///
+/// The command runs through the shell with the privileges of onefetch, in the repository
+/// directory. It is only ever taken from the command line, never from the repository itself,
+/// since running onefetch on an untrusted clone must not execute anything the clone provides.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExtraField {
+    pub label: String,
+    pub command: String,
+}
+
+impl FromStr for ExtraField {
+    type Err = String;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        match spec.split_once(':') {
+            Some((label, command)) if !label.trim().is_empty() && !command.trim().is_empty() => {
+                Ok(ExtraField {
+                    label: label.trim().to_string(),
+                    command: command.trim().to_string(),
+                })
+            }
+            _ => Err(String::from("must be given as Label:command")),
+        }
+    }
+}
+
+impl ExtraField {
+    /// Runs the command in `dir` and returns the first line of its output, trimmed. A failure,
+    /// an empty output or a command outliving the timeout yields `None`.
+    async fn run(&self, dir: &Path) -> Option<String> {
+        let output = Command::new(if cfg!(windows) { "cmd" } else { "sh" })
+            .arg(if cfg!(windows) { "/C" } else { "-c" })
+            .arg(&self.command)
+            .current_dir(dir)
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .output();
+        let output = tokio::time::timeout(TIMEOUT, output).await.ok()?.ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .next()
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())
+    }
+}
+
+/// Runs the commands of the extra fields side by side, pairing each label with its value
+pub async fn compute(fields: &[ExtraField], dir: &Path) -> Vec<(String, Option<String>)> {
+    let values = join_all(fields.iter().map(|field| field.run(dir))).await;
+    fields
+        .iter()
+        .map(|field| field.label.clone())
+        .zip(values)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn extra_fields_show_the_first_line_of_the_output() {
+        let fields: Vec<ExtraField> = [
+            "Greeting: printf '  hello  \\nworld\\n'",
+            "Failing:exit 1",
+            "Silent:true",
+        ]
+        .iter()
+        .map(|spec| spec.parse().unwrap())
+        .collect();
+        assert_eq!(fields[0].label, "Greeting");
+        assert!(ExtraField::from_str("no command").is_err());
+        assert!(ExtraField::from_str(":echo").is_err());
+
+        let values = tokio::runtime::Runtime::new()
+            .unwrap()
+            .block_on(compute(&fields, &std::env::temp_dir()));
+        assert_eq!(
+            values,
+            vec![
+                (String::from("Greeting"), Some(String::from("hello"))),
+                (String::from("Failing"), None),
+                (String::from("Silent"), None),
+            ]
+        );
+    }
+}
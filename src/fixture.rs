@@ -0,0 +1,78 @@
+//! Throwaway directories and repositories for the tests
+
+use {
+    git2::{IndexAddOption, Oid, Repository, Signature},
+    std::{
+        fs,
+        path::{Path, PathBuf},
+        sync::atomic::{AtomicUsize, Ordering},
+    },
+};
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Directory removed when dropped, named after the process and a counter so that the tests,
+/// run in parallel or by concurrent `cargo test`, never share one
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Creates a directory holding the files given as `(path, content)`, along with their parents
+pub fn dir(files: &[(&str, &str)]) -> TempDir {
+    let dir = loop {
+        let path = std::env::temp_dir().join(format!(
+            "onefetch-fixture-{}-{}",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        // A directory left by a killed run of a process with the same id is skipped
+        if fs::create_dir(&path).is_ok() {
+            break TempDir { path };
+        }
+    };
+    for (path, content) in files {
+        let path = dir.path.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+    dir
+}
+
+/// Creates a repository whose working tree holds the files, none of them being committed yet
+pub fn repo(files: &[(&str, &str)]) -> (TempDir, Repository) {
+    let dir = dir(files);
+    let repo = Repository::init(dir.path()).unwrap();
+    (dir, repo)
+}
+
+/// Commits the whole working tree on HEAD as `author`, the message telling the commits apart
+pub fn commit(repo: &Repository, author: &str, message: &str) -> Oid {
+    let mut index = repo.index().unwrap();
+    index.add_all(["*"], IndexAddOption::DEFAULT, None).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let signature = Signature::now(author, &format!("{}@example.com", author)).unwrap();
+    let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+    let parents: Vec<_> = parent.iter().collect();
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        message,
+        &tree,
+        &parents,
+    )
+    .unwrap()
+}
@@ -0,0 +1,523 @@
+use {
+    crate::{tracked_files, Error, Result},
+    git2::{Delta, Diff, DiffStats, Mailmap, Repository, Revwalk, Signature, Time},
+    std::collections::HashMap,
+};
+
+pub struct Commit {
+    pub author: String,
+    pub email: String,
+    pub author_time: Time,
+    pub commit_time: Time,
+    pub summary: String,
+    pub co_authors: Vec<String>,
+}
+
+impl Commit {
+    /// Key telling the authors apart: the lowercased email, or the lowercased name without one
+    pub fn identity(&self) -> String {
+        if self.email.is_empty() {
+            self.author.to_lowercase()
+        } else {
+            self.email.to_lowercase()
+        }
+    }
+
+    /// Seconds since the epoch in the author's local time
+    fn local_seconds(&self) -> i64 {
+        self.author_time.seconds() + i64::from(self.author_time.offset_minutes()) * 60
+    }
+
+    /// Day of the week in the author's local time, from 0 (Monday) to 6 (Sunday)
+    pub fn local_weekday(&self) -> usize {
+        weekday(self.local_seconds())
+    }
+
+    /// Day of the week in the committer's local time, from 0 (Monday) to 6 (Sunday)
+    pub fn commit_weekday(&self) -> usize {
+        weekday(self.commit_time.seconds() + i64::from(self.commit_time.offset_minutes()) * 60)
+    }
+
+    /// Year of the committer timestamp, in UTC
+    pub fn commit_year(&self) -> i64 {
+        // Converts days since the epoch to a civil date, see
+        // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let days = self.commit_time.seconds().div_euclid(86400) + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let year = year_of_era + era * 400;
+        // Years start in March in this algorithm
+        if month_index >= 10 {
+            year + 1
+        } else {
+            year
+        }
+    }
+
+    /// Hour of the day in the author's local time
+    pub fn local_hour(&self) -> usize {
+        (self.local_seconds().rem_euclid(86400) / 3600) as usize
+    }
+}
+
+/// Author the history is scoped to with `--mine`, matched on the email when there is one
+/// and on the name otherwise
+pub struct Identity {
+    pub name: Option<String>,
+    pub email: Option<String>,
+}
+
+impl Identity {
+    /// Reads `user.name` and `user.email` from the git config, returning `None` when both are unset
+    pub fn from_config(repo: &Repository) -> Option<Identity> {
+        let config = repo.config().ok()?;
+        let get = |key: &str| {
+            config
+                .get_string(key)
+                .ok()
+                .filter(|value| !value.is_empty())
+        };
+        let identity = Identity {
+            name: get("user.name"),
+            email: get("user.email"),
+        };
+        if identity.name.is_none() && identity.email.is_none() {
+            None
+        } else {
+            Some(identity)
+        }
+    }
+
+    pub fn authored(&self, commit: &git2::Commit) -> bool {
+        let author = commit.author();
+        match (&self.email, &self.name) {
+            (Some(email), _) => author.email() == Some(email),
+            (None, Some(name)) => author.name() == Some(name),
+            (None, None) => false,
+        }
+    }
+
+    /// Pattern given to `git log --fixed-strings --author`, matched against `Name <email>`
+    pub fn author_pattern(&self) -> String {
+        match (&self.email, &self.name) {
+            (Some(email), _) => format!("<{}>", email),
+            (None, Some(name)) => format!("{} <", name),
+            (None, None) => String::new(),
+        }
+    }
+}
+
+impl std::fmt::Display for Identity {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match (&self.name, &self.email) {
+            (Some(name), Some(email)) => write!(f, "{} <{}>", name, email),
+            (Some(name), None) => write!(f, "{}", name),
+            (None, Some(email)) => write!(f, "<{}>", email),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// Tells whether a commit is kept by the optional `--mine` scope
+fn in_scope(author: Option<&Identity>, commit: &git2::Commit) -> bool {
+    match author {
+        Some(author) => author.authored(commit),
+        None => true,
+    }
+}
+
+/// Day of the week of a timestamp, from 0 (Monday) to 6 (Sunday)
+fn weekday(seconds: i64) -> usize {
+    // 1970-01-01 was a Thursday
+    (seconds.div_euclid(86400) + 3).rem_euclid(7) as usize
+}
+
+/// Whether the checked out branch has no commit yet, whatever its name, unlike
+/// `Repository::is_empty` which expects the default branch
+pub fn is_unborn(repo: &Repository) -> bool {
+    matches!(repo.head(), Err(error) if error.code() == git2::ErrorCode::UnbornBranch)
+}
+
+/// Starts a walk of the history reachable from HEAD, which is empty when there are no commits
+fn walk_from_head(repo: &Repository) -> Result<Revwalk<'_>> {
+    let mut revwalk = repo.revwalk().map_err(|_| Error::ReferenceInfoError)?;
+    if !is_unborn(repo) {
+        revwalk.push_head().map_err(|_| Error::ReferenceInfoError)?;
+    }
+    Ok(revwalk)
+}
+
+/// Counts the distinct commits reachable from HEAD or any branch, remote-tracking branch or tag.
+/// A single walk from all the tips visits each commit once, however many refs reach it.
+/// Only the commits of `author` are counted when given.
+pub fn count_reachable_commits(
+    repo: &Repository,
+    no_merges: bool,
+    author: Option<&Identity>,
+) -> Result<usize> {
+    let mut revwalk = walk_from_head(repo)?;
+    for glob in &["refs/heads/*", "refs/remotes/*", "refs/tags/*"] {
+        // References to anything but a commit, like tags of trees, are skipped
+        revwalk
+            .push_glob(glob)
+            .map_err(|_| Error::ReferenceInfoError)?;
+    }
+
+    let commits = revwalk
+        .filter_map(|oid| repo.find_commit(oid.ok()?).ok())
+        .filter(|commit| !no_merges || commit.parent_count() <= 1)
+        .filter(|commit| in_scope(author, commit))
+        .count();
+
+    Ok(commits)
+}
+
+/// Walks the history reachable from HEAD, newest first, stopping after `limit` commits.
+/// Only the commits of `author` are kept when given. The authors are resolved through the
+/// mailmap of the repository.
+pub fn get_commits(
+    repo: &Repository,
+    no_merges: bool,
+    limit: Option<usize>,
+    author: Option<&Identity>,
+) -> Result<Vec<Commit>> {
+    let revwalk = walk_from_head(repo)?;
+    let mailmap = repo.mailmap().ok();
+
+    let commits = revwalk
+        .filter_map(|oid| repo.find_commit(oid.ok()?).ok())
+        .filter(|commit| !no_merges || commit.parent_count() <= 1)
+        .filter(|commit| in_scope(author, commit))
+        .take(limit.unwrap_or(usize::MAX))
+        .map(|commit| {
+            let signature = mapped_author(&commit, mailmap.as_ref());
+            Commit {
+                author: signature.name().unwrap_or_default().to_string(),
+                email: signature.email().unwrap_or_default().trim().to_string(),
+                author_time: signature.when(),
+                commit_time: commit.time(),
+                summary: commit.summary().unwrap_or_default().to_string(),
+                co_authors: parse_co_authors(commit.message().unwrap_or_default()),
+            }
+        })
+        .collect();
+
+    Ok(commits)
+}
+
+/// Returns the author of the commit as the mailmap maps it, as recorded without a mailmap
+fn mapped_author(commit: &git2::Commit, mailmap: Option<&Mailmap>) -> Signature<'static> {
+    mailmap
+        .and_then(|mailmap| commit.author_with_mailmap(mailmap).ok())
+        .unwrap_or_else(|| commit.author().to_owned())
+}
+
+/// Formats a duration in seconds the way `git log --date=relative` does, e.g. `3 hours ago`
+/// or `2 years, 5 months ago`
+pub fn format_relative_time(seconds: i64) -> String {
+    fn plural(count: i64, unit: &str) -> String {
+        match count {
+            1 => format!("1 {}", unit),
+            count => format!("{} {}s", count, unit),
+        }
+    }
+
+    if seconds < 0 {
+        return String::from("in the future");
+    }
+    if seconds < 90 {
+        return format!("{} ago", plural(seconds, "second"));
+    }
+    let minutes = (seconds + 30) / 60;
+    if minutes < 90 {
+        return format!("{} ago", plural(minutes, "minute"));
+    }
+    let hours = (minutes + 30) / 60;
+    if hours < 36 {
+        return format!("{} ago", plural(hours, "hour"));
+    }
+    let days = (hours + 12) / 24;
+    if days < 14 {
+        return format!("{} ago", plural(days, "day"));
+    }
+    if days < 70 {
+        return format!("{} ago", plural((days + 3) / 7, "week"));
+    }
+    if days < 365 {
+        return format!("{} ago", plural((days + 15) / 30, "month"));
+    }
+    if days < 1825 {
+        let total_months = (days * 12 * 2 + 365) / (365 * 2);
+        return match (total_months / 12, total_months % 12) {
+            (years, 0) => format!("{} ago", plural(years, "year")),
+            (years, months) => {
+                format!("{}, {} ago", plural(years, "year"), plural(months, "month"))
+            }
+        };
+    }
+    format!("{} ago", plural((days + 183) / 365, "year"))
+}
+
+/// Returns the names of the `Co-authored-by: Name <email>` trailers of a commit message.
+/// The key is matched case-insensitively on any line and the email is dropped;
+/// a trailer without a name is skipped.
+fn parse_co_authors(message: &str) -> Vec<String> {
+    const KEY: &str = "co-authored-by:";
+    message
+        .lines()
+        .map(str::trim)
+        .filter_map(|line| {
+            line.get(..KEY.len())
+                .filter(|key| key.eq_ignore_ascii_case(KEY))?;
+            let value = &line[KEY.len()..];
+            Some(
+                value
+                    .split('<')
+                    .next()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string(),
+            )
+        })
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+/// Counts, per author, the lines added by the commits reachable from HEAD, or by the latest
+/// `limit` ones. Merge commits are skipped since their changes belong to the merged commits.
+pub fn get_additions_by_author(
+    repo: &Repository,
+    limit: Option<usize>,
+    author: Option<&Identity>,
+) -> Result<HashMap<String, usize>> {
+    let revwalk = walk_from_head(repo)?;
+    let mailmap = repo.mailmap().ok();
+
+    let mut additions = HashMap::new();
+    for commit in revwalk
+        .filter_map(|oid| repo.find_commit(oid.ok()?).ok())
+        .filter(|commit| in_scope(author, commit))
+        .take(limit.unwrap_or(usize::MAX))
+    {
+        if commit.parent_count() > 1 {
+            continue;
+        }
+        let insertions = get_diff_stats(repo, &commit).map_or(0, |stats| stats.insertions());
+
+        let author = mapped_author(&commit, mailmap.as_ref());
+        let author = author.name().unwrap_or_default().to_string();
+        *additions.entry(author).or_insert(0) += insertions;
+    }
+
+    Ok(additions)
+}
+
+/// Counts the lines added and deleted by the commits reachable from HEAD, or by the latest
+/// `limit` ones, merges excluded
+pub fn get_churn(
+    repo: &Repository,
+    limit: Option<usize>,
+    author: Option<&Identity>,
+) -> Result<usize> {
+    let revwalk = walk_from_head(repo)?;
+
+    let churn = revwalk
+        .filter_map(|oid| repo.find_commit(oid.ok()?).ok())
+        .filter(|commit| in_scope(author, commit))
+        .take(limit.unwrap_or(usize::MAX))
+        .filter(|commit| commit.parent_count() <= 1)
+        .filter_map(|commit| get_diff_stats(repo, &commit))
+        .map(|stats| stats.insertions() + stats.deletions())
+        .sum();
+
+    Ok(churn)
+}
+
+fn get_diff_stats(repo: &Repository, commit: &git2::Commit) -> Option<DiffStats> {
+    diff_to_parent(repo, commit)?.stats().ok()
+}
+
+/// Diff of a commit against its first parent, or against the empty tree for a root commit
+fn diff_to_parent<'a>(repo: &'a Repository, commit: &git2::Commit) -> Option<Diff<'a>> {
+    let tree = commit.tree().ok();
+    let parent_tree = commit.parent(0).ok().and_then(|parent| parent.tree().ok());
+    repo.diff_tree_to_tree(parent_tree.as_ref(), tree.as_ref(), None)
+        .ok()
+}
+
+/// Returns the path of a file changed by the most recent commit, merges and commits
+/// changing only excluded files skipped. Only the latest `max_commits` commits are inspected.
+pub fn get_recently_touched(
+    repo: &Repository,
+    excluded: &[String],
+    max_commits: usize,
+) -> Result<Option<String>> {
+    let mut revwalk = repo.revwalk().map_err(|_| Error::ReferenceInfoError)?;
+    if revwalk.push_head().is_err() {
+        // Empty history
+        return Ok(None);
+    }
+    let matcher = tracked_files::build_exclude_matcher(excluded)?;
+
+    for commit in revwalk
+        .take(max_commits)
+        .filter_map(|oid| repo.find_commit(oid.ok()?).ok())
+        .filter(|commit| commit.parent_count() <= 1)
+    {
+        let diff = match diff_to_parent(repo, &commit) {
+            Some(diff) => diff,
+            None => continue,
+        };
+        let path = diff
+            .deltas()
+            .filter_map(|delta| delta.new_file().path().map(|path| path.to_path_buf()))
+            .find(|path| !matcher.matched_path_or_any_parents(path, false).is_ignore());
+        if let Some(path) = path {
+            return Ok(Some(path.to_string_lossy().into_owned()));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Returns the files of HEAD added the earliest and the latest, by the time of the commit
+/// which added them, merges and excluded files skipped. Only the latest `max_commits` commits
+/// are inspected, files added before them count as the oldest. `None` on empty history.
+pub fn get_file_ages(
+    repo: &Repository,
+    excluded: &[String],
+    max_commits: usize,
+) -> Result<Option<(String, String)>> {
+    let mut revwalk = repo.revwalk().map_err(|_| Error::ReferenceInfoError)?;
+    if revwalk.push_head().is_err() {
+        // Empty history
+        return Ok(None);
+    }
+
+    let mut added: HashMap<String, i64> = tracked_files::get_head_blobs(repo, excluded)?
+        .into_iter()
+        .map(|(path, _)| (path.to_string_lossy().into_owned(), i64::MIN))
+        .collect();
+
+    // Walking from the newest commit, the last addition seen of a path is its first one
+    for commit in revwalk
+        .take(max_commits)
+        .filter_map(|oid| repo.find_commit(oid.ok()?).ok())
+        .filter(|commit| commit.parent_count() <= 1)
+    {
+        let diff = match diff_to_parent(repo, &commit) {
+            Some(diff) => diff,
+            None => continue,
+        };
+        for delta in diff.deltas().filter(|delta| delta.status() == Delta::Added) {
+            let path = match delta.new_file().path() {
+                Some(path) => path.to_string_lossy(),
+                None => continue,
+            };
+            if let Some(time) = added.get_mut(path.as_ref()) {
+                *time = commit.time().seconds();
+            }
+        }
+    }
+
+    let oldest = added
+        .iter()
+        .min_by(|(a_path, a_time), (b_path, b_time)| a_time.cmp(b_time).then(a_path.cmp(b_path)));
+    let newest = added
+        .iter()
+        .max_by(|(a_path, a_time), (b_path, b_time)| a_time.cmp(b_time).then(b_path.cmp(a_path)));
+
+    match (oldest, newest) {
+        (Some((oldest, _)), Some((newest, _))) => Ok(Some((oldest.clone(), newest.clone()))),
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, crate::fixture, git2::Oid};
+
+    #[test]
+    fn relative_times_round_like_git() {
+        const DAY: i64 = 24 * 3600;
+        assert_eq!(format_relative_time(1), "1 second ago");
+        assert_eq!(format_relative_time(89), "89 seconds ago");
+        assert_eq!(format_relative_time(90), "2 minutes ago");
+        assert_eq!(format_relative_time(35 * 3600), "35 hours ago");
+        assert_eq!(format_relative_time(36 * 3600), "2 days ago");
+        assert_eq!(format_relative_time(20 * DAY), "3 weeks ago");
+        assert_eq!(format_relative_time(100 * DAY), "3 months ago");
+        assert_eq!(format_relative_time(365 * DAY), "1 year ago");
+        assert_eq!(format_relative_time(500 * DAY), "1 year, 4 months ago");
+        assert_eq!(format_relative_time(2000 * DAY), "5 years ago");
+        assert_eq!(format_relative_time(-5), "in the future");
+    }
+
+    #[test]
+    fn authors_are_resolved_through_the_mailmap() {
+        let (_dir, repo) = fixture::repo(&[(
+            ".mailmap",
+            "Jane Doe <jane@example.com> <jdoe@example.com>\nJane Doe <jane@example.com>\n",
+        )]);
+        fixture::commit(&repo, "jdoe", "first");
+        fixture::commit(&repo, "jane", "second");
+        fixture::commit(&repo, "bob", "third");
+
+        let authors: Vec<(String, String)> = get_commits(&repo, false, None, None)
+            .unwrap()
+            .into_iter()
+            .map(|commit| (commit.author, commit.email))
+            .collect();
+        let jane = (String::from("Jane Doe"), String::from("jane@example.com"));
+        assert_eq!(
+            authors,
+            vec![
+                (String::from("bob"), String::from("bob@example.com")),
+                jane.clone(),
+                jane
+            ]
+        );
+        assert_eq!(
+            get_additions_by_author(&repo, None, None)
+                .unwrap()
+                .get("Jane Doe"),
+            Some(&2)
+        );
+    }
+
+    #[test]
+    fn commits_reachable_from_several_refs_are_counted_once() {
+        let (_dir, repo) = fixture::repo(&[]);
+        let signature = git2::Signature::now("onefetch", "onefetch@example.com").unwrap();
+        let tree = repo
+            .find_tree(repo.index().unwrap().write_tree().unwrap())
+            .unwrap();
+        let commit = |update_ref: Option<&str>, message: &str, parents: &[Oid]| {
+            let parents: Vec<_> = parents
+                .iter()
+                .map(|parent| repo.find_commit(*parent).unwrap())
+                .collect();
+            let parents: Vec<_> = parents.iter().collect();
+            repo.commit(update_ref, &signature, &signature, message, &tree, &parents)
+                .unwrap()
+        };
+
+        let first = commit(Some("HEAD"), "first", &[]);
+        let second = commit(Some("HEAD"), "second", &[first]);
+        commit(Some("HEAD"), "third", &[second]);
+        let feature = commit(Some("refs/heads/feature"), "feature", &[second]);
+        let tagged = commit(Some("refs/remotes/origin/feature"), "tagged", &[feature]);
+        repo.reference("refs/tags/v1", tagged, false, "").unwrap();
+        repo.tag_lightweight("tree", tree.as_object(), false)
+            .unwrap();
+        commit(Some("refs/heads/merged"), "merge", &[feature, first]);
+
+        assert_eq!(count_reachable_commits(&repo, false, None).unwrap(), 6);
+        assert_eq!(count_reachable_commits(&repo, true, None).unwrap(), 5);
+        assert_eq!(get_commits(&repo, false, None, None).unwrap().len(), 3);
+    }
+}
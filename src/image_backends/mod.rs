@@ -1,4 +1,8 @@
-use image::DynamicImage;
+use {
+    crate::{Error, Result},
+    image::DynamicImage,
+    std::path::Path,
+};
 
 #[cfg(target_os = "linux")]
 pub mod kitty;
@@ -9,6 +13,12 @@ pub trait ImageBackend {
     fn add_image(&self, lines: Vec<String>, image: &DynamicImage) -> String;
 }
 
+/// Opens and decodes the image passed to `--image`
+pub fn load_image(path: &Path) -> Result<DynamicImage> {
+    image::open(path)
+        .map_err(|error| Error::ImageDecode(path.display().to_string(), error.to_string()))
+}
+
 #[cfg(target_os = "linux")]
 pub fn get_best_backend() -> Option<Box<dyn ImageBackend>> {
     if kitty::KittyBackend::supported() {
@@ -24,3 +34,22 @@ pub fn get_best_backend() -> Option<Box<dyn ImageBackend>> {
 pub fn get_best_backend() -> Option<Box<dyn ImageBackend>> {
     None
 }
+
+#[cfg(test)]
+mod test {
+    use {super::*, crate::fixture};
+
+    #[test]
+    fn non_image_files_fail_to_decode() {
+        let dir = fixture::dir(&[("not-an-image.png", "not an image")]);
+        let path = dir.path().join("not-an-image.png");
+
+        match load_image(&path) {
+            Err(Error::ImageDecode(error_path, reason)) => {
+                assert_eq!(error_path, path.display().to_string());
+                assert!(!reason.is_empty());
+            }
+            _ => panic!("a text file was decoded as an image"),
+        }
+    }
+}
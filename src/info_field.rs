@@ -0,0 +1,238 @@
+use {
+    std::str::FromStr,
+    strum::{EnumCount, EnumIter, EnumString, IntoStaticStr},
+};
+
+#[derive(Default)]
+pub struct InfoFieldOn {
+    pub git_info: bool,
+    pub project: bool,
+    pub head: bool,
+    pub branch: bool,
+    pub version: bool,
+    pub tags: bool,
+    pub created: bool,
+    pub languages: bool,
+    pub authors: bool,
+    pub last_change: bool,
+    pub recent_activity: bool,
+    pub repo: bool,
+    pub commits: bool,
+    pub pending: bool,
+    pub lines_of_code: bool,
+    pub size: bool,
+    pub files: bool,
+    pub license: bool,
+    pub path: bool,
+    pub scope: bool,
+    pub releases: bool,
+    pub packages: bool,
+    pub build: bool,
+    pub ci: bool,
+    pub deploy: bool,
+    pub formatter: bool,
+    pub base_image: bool,
+    pub ownership: bool,
+    pub recently_touched: bool,
+    pub file_ages: bool,
+    pub stars: bool,
+    pub forks: bool,
+    pub timezones: bool,
+    pub commit_style: bool,
+    pub commits_by_year: bool,
+    pub commit_clock: bool,
+    pub weekend_commits: bool,
+    pub velocity: bool,
+    pub active_this_month: bool,
+    pub co_authored: bool,
+    pub extensions: bool,
+    pub submodules: bool,
+    pub unmerged_branches: bool,
+    pub merge_ratio: bool,
+    pub branch_health: bool,
+    pub signed_by: bool,
+    pub tests: bool,
+    pub docs: bool,
+    pub tracked: bool,
+    pub indentation: bool,
+    pub dependency_licenses: bool,
+}
+
+#[derive(PartialEq, Eq, EnumString, EnumCount, EnumIter, IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum InfoFields {
+    GitInfo,
+    Project,
+    HEAD,
+    Branch,
+    Version,
+    Tags,
+    Created,
+    Languages,
+    Authors,
+    LastChange,
+    RecentActivity,
+    Repo,
+    Commits,
+    Pending,
+    LinesOfCode,
+    Size,
+    Files,
+    License,
+    Path,
+    Scope,
+    Releases,
+    Packages,
+    Build,
+    Ci,
+    Deploy,
+    Formatter,
+    BaseImage,
+    Ownership,
+    RecentlyTouched,
+    FileAges,
+    Stars,
+    Forks,
+    Timezones,
+    CommitStyle,
+    CommitsByYear,
+    CommitClock,
+    WeekendCommits,
+    Velocity,
+    ActiveThisMonth,
+    CoAuthored,
+    Extensions,
+    Submodules,
+    UnmergedBranches,
+    MergeRatio,
+    BranchHealth,
+    SignedBy,
+    Tests,
+    Docs,
+    Tracked,
+    Indentation,
+    DependencyLicenses,
+    UnrecognizedField,
+}
+
+/// Fields whose label can be renamed in the theme file
+pub const LABELED_FIELDS: [InfoFields; 50] = [
+    InfoFields::Project,
+    InfoFields::HEAD,
+    InfoFields::Branch,
+    InfoFields::Version,
+    InfoFields::Tags,
+    InfoFields::Created,
+    InfoFields::Languages,
+    InfoFields::Authors,
+    InfoFields::LastChange,
+    InfoFields::RecentActivity,
+    InfoFields::Repo,
+    InfoFields::Commits,
+    InfoFields::Pending,
+    InfoFields::LinesOfCode,
+    InfoFields::Size,
+    InfoFields::Files,
+    InfoFields::License,
+    InfoFields::Path,
+    InfoFields::Scope,
+    InfoFields::Releases,
+    InfoFields::Packages,
+    InfoFields::Build,
+    InfoFields::Ci,
+    InfoFields::Deploy,
+    InfoFields::Formatter,
+    InfoFields::BaseImage,
+    InfoFields::Ownership,
+    InfoFields::RecentlyTouched,
+    InfoFields::FileAges,
+    InfoFields::Stars,
+    InfoFields::Forks,
+    InfoFields::Timezones,
+    InfoFields::CommitStyle,
+    InfoFields::CommitsByYear,
+    InfoFields::CommitClock,
+    InfoFields::WeekendCommits,
+    InfoFields::Velocity,
+    InfoFields::ActiveThisMonth,
+    InfoFields::CoAuthored,
+    InfoFields::Extensions,
+    InfoFields::Submodules,
+    InfoFields::UnmergedBranches,
+    InfoFields::MergeRatio,
+    InfoFields::BranchHealth,
+    InfoFields::SignedBy,
+    InfoFields::Tests,
+    InfoFields::Docs,
+    InfoFields::Tracked,
+    InfoFields::Indentation,
+    InfoFields::DependencyLicenses,
+];
+
+pub fn get_disabled_fields(fields_to_hide: Vec<String>) -> InfoFieldOn {
+    let mut disabled_fields = InfoFieldOn {
+        ..Default::default()
+    };
+
+    for field in fields_to_hide.iter() {
+        let item = InfoFields::from_str(field.to_lowercase().as_str())
+            .unwrap_or(InfoFields::UnrecognizedField);
+
+        match item {
+            InfoFields::GitInfo => disabled_fields.git_info = true,
+            InfoFields::Project => disabled_fields.project = true,
+            InfoFields::HEAD => disabled_fields.head = true,
+            InfoFields::Branch => disabled_fields.branch = true,
+            InfoFields::Version => disabled_fields.version = true,
+            InfoFields::Tags => disabled_fields.tags = true,
+            InfoFields::Created => disabled_fields.created = true,
+            InfoFields::Languages => disabled_fields.languages = true,
+            InfoFields::Authors => disabled_fields.authors = true,
+            InfoFields::LastChange => disabled_fields.last_change = true,
+            InfoFields::RecentActivity => disabled_fields.recent_activity = true,
+            InfoFields::Repo => disabled_fields.repo = true,
+            InfoFields::Pending => disabled_fields.pending = true,
+            InfoFields::Commits => disabled_fields.commits = true,
+            InfoFields::LinesOfCode => disabled_fields.lines_of_code = true,
+            InfoFields::Size => disabled_fields.size = true,
+            InfoFields::Files => disabled_fields.files = true,
+            InfoFields::License => disabled_fields.license = true,
+            InfoFields::Path => disabled_fields.path = true,
+            InfoFields::Scope => disabled_fields.scope = true,
+            InfoFields::Releases => disabled_fields.releases = true,
+            InfoFields::Packages => disabled_fields.packages = true,
+            InfoFields::Build => disabled_fields.build = true,
+            InfoFields::Ci => disabled_fields.ci = true,
+            InfoFields::Deploy => disabled_fields.deploy = true,
+            InfoFields::Formatter => disabled_fields.formatter = true,
+            InfoFields::BaseImage => disabled_fields.base_image = true,
+            InfoFields::Ownership => disabled_fields.ownership = true,
+            InfoFields::RecentlyTouched => disabled_fields.recently_touched = true,
+            InfoFields::FileAges => disabled_fields.file_ages = true,
+            InfoFields::Stars => disabled_fields.stars = true,
+            InfoFields::Forks => disabled_fields.forks = true,
+            InfoFields::Timezones => disabled_fields.timezones = true,
+            InfoFields::CommitStyle => disabled_fields.commit_style = true,
+            InfoFields::CommitsByYear => disabled_fields.commits_by_year = true,
+            InfoFields::CommitClock => disabled_fields.commit_clock = true,
+            InfoFields::WeekendCommits => disabled_fields.weekend_commits = true,
+            InfoFields::Velocity => disabled_fields.velocity = true,
+            InfoFields::ActiveThisMonth => disabled_fields.active_this_month = true,
+            InfoFields::CoAuthored => disabled_fields.co_authored = true,
+            InfoFields::Extensions => disabled_fields.extensions = true,
+            InfoFields::Submodules => disabled_fields.submodules = true,
+            InfoFields::UnmergedBranches => disabled_fields.unmerged_branches = true,
+            InfoFields::MergeRatio => disabled_fields.merge_ratio = true,
+            InfoFields::BranchHealth => disabled_fields.branch_health = true,
+            InfoFields::SignedBy => disabled_fields.signed_by = true,
+            InfoFields::Tests => disabled_fields.tests = true,
+            InfoFields::Docs => disabled_fields.docs = true,
+            InfoFields::Tracked => disabled_fields.tracked = true,
+            InfoFields::Indentation => disabled_fields.indentation = true,
+            InfoFields::DependencyLicenses => disabled_fields.dependency_licenses = true,
+            _ => (),
+        }
+    }
+
+    disabled_fields
+}
@@ -1,16 +1,18 @@
 use {
-    crate::{Error, Result},
+    crate::{tracked_files, Error, Result},
     colored::Color,
+    git2::Repository,
     regex::Regex,
-    std::collections::HashMap,
+    serde::{Deserialize, Serialize},
+    std::{collections::HashMap, path::Path},
     strum::{EnumIter, EnumString},
 };
 
 macro_rules! define_languages {
-    ($( { $name:ident, $ascii:literal, $display:literal, $colors:expr $(, $serialize:literal )? } ),* ,) => {
+    ($( { $name:ident, $ascii:literal, $display:literal, $extension:literal, $colors:expr $(, $serialize:literal )? } ),* ,) => {
 
+        #[derive(PartialEq, Eq, Hash, Clone, EnumString, EnumIter, Serialize, Deserialize)]
         #[strum(serialize_all = "lowercase")]
-        #[derive(PartialEq, Eq, Hash, Clone, EnumString, EnumIter)]
         pub enum Language {
             $(
                 $( #[strum(serialize = $serialize)] )?
@@ -32,7 +34,7 @@ macro_rules! define_languages {
             fn from(language: tokei::LanguageType) -> Self {
                 match language {
                     $( tokei::LanguageType::$name => Language::$name, )*
-                        _ => unimplemented!("Language {:?}", language),
+                        _ => Language::Unknown,
                 }
             }
         }
@@ -45,6 +47,14 @@ macro_rules! define_languages {
                 }
             }
 
+            /// Returns the primary file extension of the language, `None` for `Unknown`
+            pub fn get_extension(&self) -> Option<&'static str> {
+                match *self {
+                    $( Language::$name => Some($extension), )*
+                    Language::Unknown => None,
+                }
+            }
+
             pub fn get_colors(&self) -> Vec<Color> {
                 match *self {
                     $(
@@ -101,66 +111,75 @@ macro_rules! define_languages {
 }
 
 define_languages! {
-    { Assembly, "assembly.ascii", "Assembly", vec![Color::Cyan] },
-    { C, "c.ascii", "C", vec![Color::Cyan, Color::Blue] },
-    { Clojure, "clojure.ascii", "Clojure", vec![Color::Cyan, Color::Green] },
-    { CMake, "cmake.ascii", "CMake", vec![Color::Blue, Color::Green, Color::Red, Color::Black] },
-    { CoffeeScript, "coffeescript.ascii", "CoffeeScript", vec![Color::Red] },
-    { Cpp, "cpp.ascii", "C++", vec![Color::Cyan, Color::Blue], "c++" },
-    { Crystal, "crystal.ascii", "Crystal", vec![Color::White, Color::Black] },
-    { CSharp, "csharp.ascii", "C#", vec![Color::Blue, Color::Magenta], "c#" },
-    { Css, "css.ascii", "CSS", vec![Color::Blue, Color::White] },
-    { D, "d.ascii", "D", vec![Color::Red] },
-    { Dart, "dart.ascii", "Dart", vec![Color::Cyan, Color::Blue] },
-    { Dockerfile, "dockerfile.ascii", "Dockerfile", vec![Color::Cyan, Color::White, Color::Cyan] },
-    { Elisp, "emacslisp.ascii", "EmacsLisp", vec![Color::Magenta, Color::White], "emacslisp" },
-    { Elixir, "elixir.ascii", "Elixir", vec![Color::Magenta] },
-    { Elm, "elm.ascii", "Elm", vec![Color::Black, Color::Green, Color::Yellow, Color::Cyan] },
-    { Erlang, "erlang.ascii", "Erlang", vec![Color::Red] },
-    { Fish, "fish.ascii", "Fish", vec![Color::Red, Color::Yellow] },
-    { Forth, "forth.ascii", "Forth", vec![Color::Red] },
-    { FortranModern, "f90.ascii", "Fortran", vec![Color::White, Color::Green, Color::Cyan, Color::Yellow, Color::Red], "fortran" },
-    { FSharp, "fsharp.ascii", "F#", vec![Color::Cyan, Color::Cyan], "f#" },
-    { Go, "go.ascii", "Go", vec![Color::White] },
-    { Groovy, "groovy.ascii", "Groovy", vec![Color::Cyan, Color::White] },
-    { Haskell, "haskell.ascii", "Haskell", vec![Color::Cyan, Color::Magenta, Color::Blue] },
-    { Html, "html.ascii", "HTML", vec![Color::Red, Color::White] },
-    { Idris, "idris.ascii", "Idris", vec![Color::Red] },
-    { Java, "java.ascii", "Java", vec![Color::Cyan, Color::Red] },
-    { JavaScript, "javascript.ascii", "JavaScript", vec![Color::Yellow] },
-    { Julia, "julia.ascii", "Julia", vec![Color::White, Color::Blue, Color::Green, Color::Red, Color::Magenta] },
-    { Jupyter, "jupyter.ascii", "Jupyter-Notebooks", vec![Color::White, Color::Yellow, Color::White], "jupyter-notebooks" },
-    { Kotlin, "kotlin.ascii", "Kotlin", vec![Color::Blue, Color::Yellow, Color::Magenta] },
-    { Lisp, "lisp.ascii", "Lisp", vec![Color::Yellow] },
-    { Lua, "lua.ascii", "Lua", vec![Color::Blue, Color::White] },
-    { Markdown, "markdown.ascii", "Markdown", vec![Color::White, Color::Red] },
-    { Nim, "nim.ascii", "Nim", vec![Color::Yellow, Color::White] },
-    { Nix, "nix.ascii", "Nix", vec![Color::Cyan, Color::Blue] },
-    { ObjectiveC, "objectivec.ascii", "Objective-C", vec![Color::Cyan, Color::Blue], "objective-c" },
-    { OCaml, "ocaml.ascii", "OCaml", vec![Color::Yellow] },
-    { Org, "org.ascii", "Org", vec![Color::Green, Color::Red, Color::White] },
-    { Perl, "perl.ascii", "Perl", vec![Color::Cyan] },
-    { Php, "php.ascii", "Php", vec![Color::Magenta, Color::Blue, Color::Cyan, Color::White] },
-    { Prolog, "prolog.ascii", "Prolog", vec![Color::Blue, Color::Red] },
-    { PureScript, "purescript.ascii", "PureScript", vec![Color::White] },
-    { Python, "python.ascii", "Python", vec![Color::Blue, Color::Yellow] },
-    { R, "r.ascii", "R", vec![Color::White, Color::Blue] },
-    { Racket, "racket.ascii", "Racket", vec![Color::Red, Color::White, Color::Blue] },
-    { Ruby, "ruby.ascii", "Ruby", vec![Color::Magenta] },
-    { Rust, "rust.ascii", "Rust", vec![Color::White, Color::Red] },
-    { Scala, "scala.ascii", "Scala", vec![Color::Blue] },
-    { Sh, "shell.ascii", "Shell", vec![Color::Green], "shell" },
-    { Swift, "swift.ascii", "Swift", vec![Color::Red] },
-    { Tcl, "tcl.ascii", "Tcl", vec![Color::Blue, Color::White, Color::Cyan] },
-    { Tex, "tex.ascii", "Tex", vec![Color::White, Color::Black] },
-    { TypeScript, "typescript.ascii", "TypeScript", vec![Color::Cyan] },
-    { Vue, "vue.ascii", "Vue", vec![Color::Green, Color::Blue] },
-    { Xml, "xml.ascii", "XML", vec![Color::Yellow, Color::White, Color::Green] },
-    { Zig, "zig.ascii", "Zig", vec![Color::Yellow] },
+    { Assembly, "assembly.ascii", "Assembly", "asm", vec![Color::Cyan] },
+    { C, "c.ascii", "C", "c", vec![Color::Cyan, Color::Blue] },
+    { Clojure, "clojure.ascii", "Clojure", "clj", vec![Color::Cyan, Color::Green] },
+    { CMake, "cmake.ascii", "CMake", "cmake", vec![Color::Blue, Color::Green, Color::Red, Color::Black] },
+    { CoffeeScript, "coffeescript.ascii", "CoffeeScript", "coffee", vec![Color::Red] },
+    { Cpp, "cpp.ascii", "C++", "cpp", vec![Color::Cyan, Color::Blue], "c++" },
+    { Crystal, "crystal.ascii", "Crystal", "cr", vec![Color::White, Color::Black] },
+    { CSharp, "csharp.ascii", "C#", "cs", vec![Color::Blue, Color::Magenta], "c#" },
+    { Css, "css.ascii", "CSS", "css", vec![Color::Blue, Color::White] },
+    { D, "d.ascii", "D", "d", vec![Color::Red] },
+    { Dart, "dart.ascii", "Dart", "dart", vec![Color::Cyan, Color::Blue] },
+    { Dockerfile, "dockerfile.ascii", "Dockerfile", "dockerfile", vec![Color::Cyan, Color::White, Color::Cyan] },
+    { Elisp, "emacslisp.ascii", "EmacsLisp", "el", vec![Color::Magenta, Color::White], "emacslisp" },
+    { Elixir, "elixir.ascii", "Elixir", "ex", vec![Color::Magenta] },
+    { Elm, "elm.ascii", "Elm", "elm", vec![Color::Black, Color::Green, Color::Yellow, Color::Cyan] },
+    { Erlang, "erlang.ascii", "Erlang", "erl", vec![Color::Red] },
+    { Fish, "fish.ascii", "Fish", "fish", vec![Color::Red, Color::Yellow] },
+    { Forth, "forth.ascii", "Forth", "fth", vec![Color::Red] },
+    { FortranModern, "f90.ascii", "Fortran", "f90", vec![Color::White, Color::Green, Color::Cyan, Color::Yellow, Color::Red], "fortran" },
+    { FSharp, "fsharp.ascii", "F#", "fs", vec![Color::Cyan, Color::Cyan], "f#" },
+    { Go, "go.ascii", "Go", "go", vec![Color::White] },
+    { Groovy, "groovy.ascii", "Groovy", "groovy", vec![Color::Cyan, Color::White] },
+    { Haskell, "haskell.ascii", "Haskell", "hs", vec![Color::Cyan, Color::Magenta, Color::Blue] },
+    { Haxe, "haxe.ascii", "Haxe", "hx", vec![Color::Yellow, Color::Red] },
+    { Html, "html.ascii", "HTML", "html", vec![Color::Red, Color::White] },
+    { Idris, "idris.ascii", "Idris", "idr", vec![Color::Red] },
+    { Java, "java.ascii", "Java", "java", vec![Color::Cyan, Color::Red] },
+    { JavaScript, "javascript.ascii", "JavaScript", "js", vec![Color::Yellow] },
+    { Julia, "julia.ascii", "Julia", "jl", vec![Color::White, Color::Blue, Color::Green, Color::Red, Color::Magenta] },
+    { Jupyter, "jupyter.ascii", "Jupyter-Notebooks", "ipynb", vec![Color::White, Color::Yellow, Color::White], "jupyter-notebooks" },
+    { Kotlin, "kotlin.ascii", "Kotlin", "kt", vec![Color::Blue, Color::Yellow, Color::Magenta] },
+    { Lisp, "lisp.ascii", "Lisp", "lisp", vec![Color::Yellow] },
+    { Lua, "lua.ascii", "Lua", "lua", vec![Color::Blue, Color::White] },
+    { Markdown, "markdown.ascii", "Markdown", "md", vec![Color::White, Color::Red] },
+    { Nim, "nim.ascii", "Nim", "nim", vec![Color::Yellow, Color::White] },
+    { Nix, "nix.ascii", "Nix", "nix", vec![Color::Cyan, Color::Blue] },
+    { ObjectiveC, "objectivec.ascii", "Objective-C", "m", vec![Color::Cyan, Color::Blue], "objective-c" },
+    { OCaml, "ocaml.ascii", "OCaml", "ml", vec![Color::Yellow] },
+    { Org, "org.ascii", "Org", "org", vec![Color::Green, Color::Red, Color::White] },
+    { Perl, "perl.ascii", "Perl", "pl", vec![Color::Cyan] },
+    { Php, "php.ascii", "Php", "php", vec![Color::Magenta, Color::Blue, Color::Cyan, Color::White] },
+    { Prolog, "prolog.ascii", "Prolog", "pro", vec![Color::Blue, Color::Red] },
+    { PureScript, "purescript.ascii", "PureScript", "purs", vec![Color::White] },
+    { Python, "python.ascii", "Python", "py", vec![Color::Blue, Color::Yellow] },
+    { R, "r.ascii", "R", "r", vec![Color::White, Color::Blue] },
+    { Racket, "racket.ascii", "Racket", "rkt", vec![Color::Red, Color::White, Color::Blue] },
+    { Ruby, "ruby.ascii", "Ruby", "rb", vec![Color::Magenta] },
+    { Rust, "rust.ascii", "Rust", "rs", vec![Color::White, Color::Red] },
+    { Scala, "scala.ascii", "Scala", "scala", vec![Color::Blue] },
+    { Sh, "shell.ascii", "Shell", "sh", vec![Color::Green], "shell" },
+    { Solidity, "solidity.ascii", "Solidity", "sol", vec![Color::White, Color::BrightBlack] },
+    { Swift, "swift.ascii", "Swift", "swift", vec![Color::Red] },
+    { Tcl, "tcl.ascii", "Tcl", "tcl", vec![Color::Blue, Color::White, Color::Cyan] },
+    { Tex, "tex.ascii", "Tex", "tex", vec![Color::White, Color::Black] },
+    { TypeScript, "typescript.ascii", "TypeScript", "ts", vec![Color::Cyan] },
+    { Vue, "vue.ascii", "Vue", "vue", vec![Color::Green, Color::Blue] },
+    { Xml, "xml.ascii", "XML", "xml", vec![Color::Yellow, Color::White, Color::Green] },
+    { Zig, "zig.ascii", "Zig", "zig", vec![Color::Yellow] },
 }
 
+/// Number of files, lines of code and lines of code in test files of each language
+pub type LanguagePresence = HashMap<Language, (usize, usize, usize)>;
+
+/// Languages sorted by share of the lines of code, total lines of code and presence
+pub type LanguageStats = (Vec<(Language, f64, usize)>, usize, LanguagePresence);
+
 impl Language {
-    fn get_languages_stat(languages: &tokei::Languages) -> Option<HashMap<Language, f64>> {
+    /// Returns the share and the lines of code of each language
+    fn get_languages_stat(languages: &tokei::Languages) -> Option<HashMap<Language, (f64, usize)>> {
         let mut stats = HashMap::new();
 
         let sum_language_code: usize = languages.iter().map(|(_, v)| v.code).sum();
@@ -169,31 +188,116 @@ impl Language {
             None
         } else {
             for (k, v) in languages.iter() {
-                let code = v.code as f64;
-                stats.insert(
-                    Language::from(*k),
-                    (code / sum_language_code as f64) * 100.00,
-                );
+                let (share, lines) = stats.entry(Language::from(*k)).or_insert((0.0, 0));
+                *share += (v.code as f64 / sum_language_code as f64) * 100.00;
+                *lines += v.code;
             }
             Some(stats)
         }
     }
 
-    pub fn get_language_stats(
+    pub fn get_language_stats(dir: &str, ignored_directories: Vec<&str>) -> Result<LanguageStats> {
+        let tokei_langs = project_languages(dir, &ignored_directories, true);
+        let root = Path::new(dir);
+        match Language::sort_language_stats(&tokei_langs, root) {
+            // Files of languages without a logo are counted as `Unknown`, only when there are no others
+            Err(Error::SourceCodeNotFound) => Language::sort_language_stats(
+                &project_languages(dir, &ignored_directories, false),
+                root,
+            ),
+            stats => stats,
+        }
+    }
+
+    /// Same as `get_language_stats`, counting the files committed in HEAD instead of the
+    /// working tree, so that uncommitted changes and untracked files are left out
+    pub fn get_committed_language_stats(
+        repo: &Repository,
+        excluded: &[String],
+    ) -> Result<LanguageStats> {
+        let tokei_langs = committed_languages(repo, excluded, true)?;
+        // The paths of the committed files are already relative to the root of the repository
+        let root = Path::new("");
+        match Language::sort_language_stats(&tokei_langs, root) {
+            Err(Error::SourceCodeNotFound) => {
+                Language::sort_language_stats(&committed_languages(repo, excluded, false)?, root)
+            }
+            stats => stats,
+        }
+    }
+
+    /// Returns the tokei language types found in the directory which don't map to a `Language`
+    pub fn get_unmapped_language_types(
         dir: &str,
         ignored_directories: Vec<&str>,
-    ) -> Result<(Vec<(Language, f64)>, usize)> {
-        let tokei_langs = project_languages(&dir, ignored_directories);
+    ) -> Vec<tokei::LanguageType> {
+        let required_languages = get_all_language_types();
+        let mut types: Vec<tokei::LanguageType> =
+            project_languages(dir, &ignored_directories, false)
+                .iter()
+                .filter(|(language_type, language)| {
+                    language.code > 0 && !required_languages.contains(language_type)
+                })
+                .map(|(language_type, _)| *language_type)
+                .collect();
+        types.sort();
+        types
+    }
+
+    /// Sorts the languages by share, the paths of the reports being relative to `root`
+    fn sort_language_stats(tokei_langs: &tokei::Languages, root: &Path) -> Result<LanguageStats> {
         let languages_stat =
-            Language::get_languages_stat(&tokei_langs).ok_or(Error::SourceCodeNotFound)?;
-        let mut stat_vec: Vec<(_, _)> = languages_stat.into_iter().collect();
-        stat_vec.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap().reverse());
-        let loc = get_total_loc(&tokei_langs);
-        Ok((stat_vec, loc))
+            Language::get_languages_stat(tokei_langs).ok_or(Error::SourceCodeNotFound)?;
+        let mut stat_vec: Vec<(_, _, _)> = languages_stat
+            .into_iter()
+            .map(|(language, (share, lines))| (language, share, lines))
+            .collect();
+        stat_vec.sort_by(|a, b| {
+            a.1.partial_cmp(&b.1)
+                .unwrap()
+                .reverse()
+                .then_with(|| a.0.to_string().cmp(&b.0.to_string()))
+        });
+        let loc = get_total_loc(tokei_langs);
+
+        let mut presence = LanguagePresence::new();
+        for (language_type, language) in tokei_langs.iter() {
+            let (files, lines, test_lines) =
+                presence.entry(Language::from(*language_type)).or_default();
+            *files += language.reports.len();
+            *lines += language.code;
+            *test_lines += language
+                .reports
+                .iter()
+                .filter(|report| {
+                    tracked_files::is_test_file(
+                        report.name.strip_prefix(root).unwrap_or(&report.name),
+                    )
+                })
+                .map(|report| report.stats.code)
+                .sum::<usize>();
+        }
+        Ok((stat_vec, loc, presence))
     }
 
-    pub async fn get_dominant_language(languages_stat_vec: &[(Language, f64)]) -> Language {
-        languages_stat_vec[0].0.clone()
+    /// Returns the language with the largest share among those with at least `min_files` files
+    /// and `min_lines` lines of code, or the one with the largest share when none qualifies
+    pub fn get_dominant_language(
+        languages_stat_vec: &[(Language, f64, usize)],
+        presence: &LanguagePresence,
+        min_files: usize,
+        min_lines: usize,
+    ) -> Language {
+        languages_stat_vec
+            .iter()
+            .map(|(language, _, _)| language)
+            .find(|language| match presence.get(language) {
+                Some((files, lines, _)) => *files >= min_files && *lines >= min_lines,
+                None => false,
+            })
+            .or_else(|| languages_stat_vec.first().map(|(language, _, _)| language))
+            .cloned()
+            .unwrap_or(Language::Unknown)
     }
 }
 
@@ -205,20 +309,25 @@ fn get_total_loc(languages: &tokei::Languages) -> usize {
         .fold(0, |sum, val| sum + val.code)
 }
 
-fn project_languages(dir: &str, ignored_directories: Vec<&str>) -> tokei::Languages {
+/// Counts the lines of code of the directory, of the supported languages only
+/// unless `supported_only` is false
+fn project_languages(
+    dir: &str,
+    ignored_directories: &[&str],
+    supported_only: bool,
+) -> tokei::Languages {
     use tokei::Config;
 
     let mut languages = tokei::Languages::new();
-    let required_languages = get_all_language_types();
     let tokei_config = Config {
-        types: Some(required_languages),
+        types: Some(get_all_language_types()).filter(|_| supported_only),
         ..Config::default()
     };
 
     if !ignored_directories.is_empty() {
         let re = Regex::new(r"((.*)+/)+(.*)").unwrap();
         let mut v = Vec::with_capacity(ignored_directories.len());
-        for ignored in ignored_directories {
+        for &ignored in ignored_directories {
             if re.is_match(ignored) {
                 let p = if ignored.starts_with('/') {
                     "**"
@@ -233,8 +342,115 @@ fn project_languages(dir: &str, ignored_directories: Vec<&str>) -> tokei::Langua
         let ignored_directories_for_ab: Vec<&str> = v.iter().map(|x| &**x).collect();
         languages.get_statistics(&[&dir], &ignored_directories_for_ab, &tokei_config);
     } else {
-        languages.get_statistics(&[&dir], &ignored_directories, &tokei_config);
+        languages.get_statistics(&[&dir], ignored_directories, &tokei_config);
     }
 
     languages
 }
+
+fn committed_languages(
+    repo: &Repository,
+    excluded: &[String],
+    supported_only: bool,
+) -> Result<tokei::Languages> {
+    let mut languages = tokei::Languages::new();
+    let required_languages = get_all_language_types();
+    let tokei_config = tokei::Config::default();
+
+    for (path, oid) in tracked_files::get_head_blobs(repo, excluded)? {
+        let language_type = match tokei::LanguageType::from_path(&path, &tokei_config) {
+            Some(language_type)
+                if !supported_only || required_languages.contains(&language_type) =>
+            {
+                language_type
+            }
+            _ => continue,
+        };
+        if let Ok(blob) = repo.find_blob(oid) {
+            let mut report = tokei::Report::new(path);
+            report.stats = language_type.parse_from_slice(blob.content(), &tokei_config);
+            languages
+                .entry(language_type)
+                .or_default()
+                .add_report(report);
+        }
+    }
+
+    for language in languages.values_mut() {
+        language.total();
+    }
+
+    Ok(languages)
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, crate::fixture};
+
+    #[test]
+    fn language_stats_order_is_deterministic() {
+        let dir = fixture::dir(&[
+            ("main.rs", "fn main() {\n}\n"),
+            ("main.go", "func main() {\n}\n"),
+            ("main.py", "def main():\n    pass\n"),
+        ]);
+
+        let order = || {
+            let (stats, _, _) =
+                Language::get_language_stats(dir.path().to_str().unwrap(), vec![]).unwrap();
+            stats
+                .into_iter()
+                .map(|(language, _, _)| language.to_string())
+                .collect::<Vec<_>>()
+        };
+        let first = order();
+        assert_eq!(first, vec!["Go", "Python", "Rust"]);
+        for _ in 0..10 {
+            assert_eq!(order(), first);
+        }
+    }
+
+    #[test]
+    fn unsupported_languages_resolve_to_unknown() {
+        let dir = fixture::dir(&[("query.sql", "SELECT 1;\n")]);
+
+        let (stats, _, _) =
+            Language::get_language_stats(dir.path().to_str().unwrap(), vec![]).unwrap();
+        assert_eq!(stats.len(), 1);
+        assert!(stats[0].0 == Language::Unknown);
+        assert_eq!(
+            Language::get_unmapped_language_types(dir.path().to_str().unwrap(), vec![]),
+            vec![tokei::LanguageType::Sql]
+        );
+    }
+
+    #[test]
+    fn lines_of_test_files_are_counted_apart() {
+        let dir = fixture::dir(&[
+            ("src/main.rs", "fn main() {\n}\n".repeat(3).as_str()),
+            ("tests/cli.rs", "fn it() {\n}\n"),
+        ]);
+
+        let (_, _, presence) =
+            Language::get_language_stats(dir.path().to_str().unwrap(), vec![]).unwrap();
+        assert_eq!(presence.get(&Language::Rust), Some(&(2, 8, 2)));
+    }
+
+    #[test]
+    fn dominant_language_requires_the_minimum_presence() {
+        let dir = fixture::dir(&[
+            ("generated.go", "var x = 1\n".repeat(100).as_str()),
+            ("a.rs", "fn main() {\n}\n"),
+            ("b.rs", "fn main() {\n}\n"),
+            ("c.rs", "fn main() {\n}\n"),
+        ]);
+
+        let (stats, _, presence) =
+            Language::get_language_stats(dir.path().to_str().unwrap(), vec![]).unwrap();
+        assert!(stats[0].0 == Language::Go);
+        assert_eq!(stats[0].2, 100);
+        assert!(Language::get_dominant_language(&stats, &presence, 0, 0) == Language::Go);
+        assert!(Language::get_dominant_language(&stats, &presence, 2, 0) == Language::Rust);
+        assert!(Language::get_dominant_language(&stats, &presence, 2, 10) == Language::Go);
+    }
+}
@@ -1,72 +1,58 @@
 #[macro_use]
 extern crate clap;
 
-#[cfg(target_os = "linux")]
-use image_backends::ImageBackend;
 use {
     ascii_art::AsciiArt,
-    clap::{App, Arg},
-    colored::*,
+    cli::{Cli, Output, OutputFormat},
     commit_info::CommitInfo,
     error::Error,
     info::Info,
     language::Language,
     std::{
-        convert::From,
+        io::{self, Write},
         process::{Command, Stdio},
         result,
-        str::FromStr,
     },
-    strum::{EnumCount, EnumIter, EnumString, IntoEnumIterator, IntoStaticStr},
+    strum::IntoEnumIterator,
 };
 
 mod ascii_art;
+mod blame;
+mod border;
+mod cli;
+mod clone;
 mod commit_info;
+mod compare;
+mod dependencies;
+mod detection;
+mod dockerfile;
 mod error;
+mod export;
+mod extra_field;
+#[cfg(test)]
+mod fixture;
+mod forge;
+mod history;
 mod image_backends;
 mod info;
+mod info_field;
 mod language;
 mod license;
+mod monorepo;
+mod pager;
+mod qr;
+mod signing;
+mod stats_cache;
+mod theme;
+mod tracked_files;
+mod watch;
 
 type Result<T> = result::Result<T, Error>;
 
-#[derive(Default)]
-pub struct InfoFieldOn {
-    git_info: bool,
-    project: bool,
-    head: bool,
-    version: bool,
-    created: bool,
-    languages: bool,
-    authors: bool,
-    last_change: bool,
-    repo: bool,
-    commits: bool,
-    pending: bool,
-    lines_of_code: bool,
-    size: bool,
-    license: bool,
-}
-
-#[derive(PartialEq, Eq, EnumString, EnumCount, EnumIter, IntoStaticStr)]
-#[strum(serialize_all = "snake_case")]
-enum InfoFields {
-    GitInfo,
-    Project,
-    HEAD,
-    Version,
-    Created,
-    Languages,
-    Authors,
-    LastChange,
-    Repo,
-    Commits,
-    Pending,
-    LinesOfCode,
-    Size,
-    License,
-    UnrecognizedField,
-}
+/// Exit code bit set by `--exit-dirty` when the working tree has pending changes
+const EXIT_DIRTY: i32 = 2;
+/// Exit code bit set by `--exit-no-license` when no license is detected
+const EXIT_NO_LICENSE: i32 = 4;
 
 fn main() -> Result<()> {
     #[cfg(target_os = "windows")]
@@ -79,140 +65,17 @@ fn main() -> Result<()> {
         colored::control::set_override(true);
     }
 
-    if !is_git_installed() {
+    let mut config = Cli::new()?;
+
+    if !config.no_git && !is_git_installed() {
         return Err(Error::GitNotInstalled);
     }
 
-    let possible_languages: Vec<String> = Language::iter()
-        .filter(|language| *language != Language::Unknown)
-        .map(|language| language.to_string().to_lowercase())
-        .collect();
-
-    #[cfg(target_os = "linux")]
-    let possible_backends = ["kitty", "sixel"];
-    #[cfg(not(target_os = "linux"))]
-    let possible_backends = [];
-
-    let matches = App::new(crate_name!())
-        .version(crate_version!())
-        .author("o2sh <ossama-hjaji@live.fr>")
-        .about(crate_description!())
-        .arg(Arg::with_name("input").default_value(".").help(
-            "Run as if onefetch was started in <input> instead of the current working directory.",
-        ))
-        .arg(
-            Arg::with_name("ascii-language")
-                .short("a")
-                .long("ascii-language")
-                .takes_value(true)
-                .possible_values(
-                    &possible_languages
-                        .iter()
-                        .map(|l| l.as_str())
-                        .collect::<Vec<&str>>(),
-                )
-                .case_insensitive(true)
-                .help("Which language's ascii art to print."),
-        )
-        .arg(
-            Arg::with_name("disable-fields")
-                .long("disable-fields")
-                .short("d")
-                .multiple(true)
-                .takes_value(true)
-                .case_insensitive(true)
-                .help("Allows you to disable an info line from appearing in the output.")
-                .possible_values(
-                    &InfoFields::iter()
-                        .take(InfoFields::COUNT - 1)
-                        .map(|field| field.into())
-                        .collect::<Vec<&str>>()
-                        .as_slice(),
-                ),
-        )
-        .arg(
-            Arg::with_name("ascii-colors")
-                .short("c")
-                .long("ascii-colors")
-                .multiple(true)
-                .takes_value(true)
-                .possible_values(&[
-                    "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12", "13", "14",
-                    "15",
-                ])
-                .hide_possible_values(true)
-                .help(&format!(
-                    "Colors to print the ascii art. Possible values: [{0}{1}{2}{3}{4}{5}{6}{7}]",
-                    "0".black(),
-                    "1".red(),
-                    "2".green(),
-                    "3".yellow(),
-                    "4".blue(),
-                    "5".magenta(),
-                    "6".cyan(),
-                    "7".white()
-                )),
-        )
-        .arg(
-            Arg::with_name("no-bold")
-                .long("no-bold")
-                .help("Turns off bold formatting."),
-        )
-        .arg(
-            Arg::with_name("languages")
-                .short("l")
-                .long("languages")
-                .help("Prints out supported languages"),
-        )
-        .arg(
-            Arg::with_name("image")
-                .short("i")
-                .long("image")
-                .takes_value(true)
-                .help("Which image to use. Possible values: [/path/to/img]"),
-        )
-        .arg(
-            Arg::with_name("image-backend")
-                .long("image-backend")
-                .takes_value(true)
-                .possible_values(&possible_backends)
-                .help("Which image backend to use."),
-        )
-        .arg(
-            Arg::with_name("no-merge-commits")
-                .long("no-merge-commits")
-                .help("Ignores merge commits"),
-        )
-        .arg(
-            Arg::with_name("no-color-blocks")
-                .long("no-color-blocks")
-                .help("Hides the color blocks"),
-        )
-        .arg(
-            Arg::with_name("authors-number")
-                .short("A")
-                .long("authors-number")
-                .takes_value(true)
-                .default_value("3")
-                .help("Number of authors to be shown."),
-        )
-        .arg(
-            Arg::with_name("exclude")
-                .short("e")
-                .long("exclude")
-                .multiple(true)
-                .takes_value(true)
-                .help("Ignore all files & directories matching the pattern."),
-        )
-        .get_matches();
-
-    let ignored_directories: Vec<&str> = if let Some(user_ignored) = matches.values_of("exclude") {
-        user_ignored.map(|s| s as &str).collect()
-    } else {
-        Vec::new()
-    };
+    if config.verbose && config.auto_exclude {
+        eprintln!("Auto-excluded: {}", cli::AUTO_EXCLUDED.join(", "));
+    }
 
-    if matches.is_present("languages") {
+    if config.print_languages {
         let iterator = Language::iter().filter(|x| *x != Language::Unknown);
 
         for l in iterator {
@@ -221,107 +84,138 @@ fn main() -> Result<()> {
         std::process::exit(0);
     }
 
-    let dir = String::from(matches.value_of("input").unwrap());
-
-    let custom_logo: Language = if let Some(ascii_language) = matches.value_of("ascii-language") {
-        Language::from_str(&ascii_language.to_lowercase()).unwrap()
-    } else {
-        Language::Unknown
-    };
-    let mut disable_fields = InfoFieldOn {
-        ..Default::default()
+    // Kept alive until the end of main, the temporary clone being removed when dropped
+    let clone = match &config.repo_url {
+        Some(url) => Some(clone::TempClone::new(url, config.depth)?),
+        None => None,
     };
+    if let Some(clone) = &clone {
+        config.path = clone.path().to_string_lossy().into_owned();
+    }
 
-    let fields_to_hide: Vec<String> = if let Some(values) = matches.values_of("disable-fields") {
-        values.map(String::from).collect()
-    } else {
-        Vec::new()
-    };
+    if config.output == Some(Output::DominantExtension) {
+        let language = Info::get_dominant_language(&config)?;
+        println!("{}", language.get_extension().unwrap_or_default());
+        return Ok(());
+    }
 
-    for field in fields_to_hide.iter() {
-        let item = InfoFields::from_str(field.to_lowercase().as_str())
-            .unwrap_or(InfoFields::UnrecognizedField);
+    if config.output == Some(Output::GitVersion) {
+        println!("{}", Info::get_git_version()?);
+        return Ok(());
+    }
 
-        match item {
-            InfoFields::GitInfo => disable_fields.git_info = true,
-            InfoFields::Project => disable_fields.project = true,
-            InfoFields::HEAD => disable_fields.head = true,
-            InfoFields::Version => disable_fields.version = true,
-            InfoFields::Created => disable_fields.created = true,
-            InfoFields::Languages => disable_fields.languages = true,
-            InfoFields::Authors => disable_fields.authors = true,
-            InfoFields::LastChange => disable_fields.last_change = true,
-            InfoFields::Repo => disable_fields.repo = true,
-            InfoFields::Pending => disable_fields.pending = true,
-            InfoFields::Commits => disable_fields.commits = true,
-            InfoFields::LinesOfCode => disable_fields.lines_of_code = true,
-            InfoFields::Size => disable_fields.size = true,
-            InfoFields::License => disable_fields.license = true,
-            _ => (),
-        }
+    if config.logo_only {
+        print!("{}", Info::get_logo(&config)?);
+        return Ok(());
     }
 
-    let custom_colors: Vec<String> = if let Some(values) = matches.values_of("ascii-colors") {
-        values.map(String::from).collect()
-    } else {
-        Vec::new()
-    };
+    if config.watch {
+        let repo = git2::Repository::discover(&config.path).map_err(|_| Error::NotGitRepo)?;
+        let workdir = repo.workdir().ok_or(Error::BareGitRepo)?;
+        let worktree =
+            Some((workdir, config.excluded.as_slice())).filter(|_| config.watch_worktree);
+        // The arguments are parsed again on each render since `Cli` can't be cloned
+        return watch::watch(
+            || Ok(Info::new(Cli::new()?)?.to_string()),
+            repo.path(),
+            worktree,
+        );
+    }
 
-    let bold_flag = !matches.is_present("no-bold");
+    if let Some(other_path) = config.compare.clone() {
+        // Both sides are measured with the same options, parsed again since `Cli` can't be cloned
+        let mut other_config = Cli::new()?;
+        other_config.path = other_path.clone();
+        let path = config.path.clone();
+        let info = Info::new(config).map_err(|error| Error::Compare(path, Box::new(error)))?;
+        let other_info =
+            Info::new(other_config).map_err(|error| Error::Compare(other_path, Box::new(error)))?;
+
+        print!("{}", compare::Comparison::new(&info, &other_info));
+        return Ok(());
+    }
 
-    let custom_image = if let Some(image_path) = matches.value_of("image") {
-        Some(image::open(image_path).map_err(|_| Error::ImageLoadError)?)
+    let show_qr = config.qr;
+    let export_path = config.export.clone();
+    let (exit_dirty, exit_no_license) = (config.exit_dirty, config.exit_no_license);
+    let print_dimensions = config.print_dimensions;
+    let use_pager = config.pager;
+    let border = config.border;
+    let format = config.format;
+    let info = Info::new(config)?;
+
+    if format == OutputFormat::Json {
+        println!("{}", info.to_json());
+    } else if !print_dimensions && export_path.is_none() && border.is_none() && !use_pager {
+        // The lines are printed as they are rendered, unless the whole output is needed first
+        print!("{}", info);
+        if show_qr {
+            print!(
+                "{}",
+                render_qr(&info.repo_url(), info.unicode()).unwrap_or_default()
+            );
+        }
     } else {
-        None
-    };
+        let mut rendered = info.to_string();
+        if let Some(style) = border {
+            rendered = border::draw(&rendered, style);
+        }
 
-    let image_backend = if custom_image.is_some() {
-        if let Some(backend_name) = matches.value_of("image-backend") {
-            #[cfg(target_os = "linux")]
-            let backend =
-                Some(match backend_name {
-                    "kitty" => Box::new(image_backends::kitty::KittyBackend::new())
-                        as Box<dyn ImageBackend>,
-                    "sixel" => Box::new(image_backends::sixel::SixelBackend::new())
-                        as Box<dyn ImageBackend>,
-                    _ => unreachable!(),
-                });
-            #[cfg(not(target_os = "linux"))]
-            let backend = None;
-            backend
-        } else {
-            crate::image_backends::get_best_backend()
+        if print_dimensions {
+            let columns = rendered.lines().map(info::display_width).max().unwrap_or(0);
+            eprintln!("{}x{}", columns, rendered.lines().count());
         }
-    } else {
-        None
-    };
 
-    let no_merges = matches.is_present("no-merge-commits");
+        if let Some(path) = &export_path {
+            export::export(&rendered, path)?;
+        } else {
+            let mut output = rendered;
+            if show_qr {
+                output.push_str(&render_qr(&info.repo_url(), info.unicode()).unwrap_or_default());
+            }
+
+            if !use_pager || !pager::page(&output) {
+                print!("{}", output);
+            }
+        }
+    }
 
-    let color_blocks_flag = matches.is_present("no-color-blocks");
+    let mut exit_code = 0;
+    if exit_dirty && info.is_dirty() {
+        exit_code |= EXIT_DIRTY;
+    }
+    if exit_no_license && !info.has_license() {
+        exit_code |= EXIT_NO_LICENSE;
+    }
+    if exit_code != 0 {
+        io::stdout().flush().ok();
+        drop(clone);
+        std::process::exit(exit_code);
+    }
+    Ok(())
+}
 
-    let author_number: usize = if let Some(value) = matches.value_of("authors-number") {
-        usize::from_str(value).unwrap()
+/// Renders a QR code of the repository URL, unless there is none or the terminal is too narrow
+fn render_qr(repo_url: &str, unicode: bool) -> Option<String> {
+    if repo_url.is_empty() {
+        return None;
+    }
+    let code = qr::QrCode::new(repo_url)?;
+    let width = if unicode {
+        code.width()
     } else {
-        3
+        2 * code.width()
     };
-
-    let info = Info::new(
-        &dir,
-        custom_logo,
-        custom_colors,
-        disable_fields,
-        bold_flag,
-        custom_image,
-        image_backend,
-        no_merges,
-        color_blocks_flag,
-        author_number,
-        ignored_directories,
-    )?;
-
-    print!("{}", info);
-    Ok(())
+    if let Some((terminal_width, _)) = term_size::dimensions() {
+        if width > terminal_width {
+            return None;
+        }
+    }
+    if unicode {
+        Some(code.render())
+    } else {
+        Some(code.render_ascii())
+    }
 }
 
 fn is_git_installed() -> bool {
@@ -0,0 +1,178 @@
+use {
+    crate::language::Language,
+    ignore::{overrides::OverrideBuilder, WalkBuilder},
+    std::{
+        fs,
+        path::{Path, PathBuf},
+    },
+};
+
+/// Files marking the root of a package (Cargo workspace member, npm package, ...)
+const MANIFEST_FILES: [&str; 7] = [
+    "Cargo.toml",
+    "package.json",
+    "go.mod",
+    "pyproject.toml",
+    "setup.py",
+    "pom.xml",
+    "build.gradle",
+];
+const MAX_DEPTH: usize = 4;
+const MAX_PACKAGES: usize = 8;
+
+pub struct Package {
+    pub path: String,
+    pub language: Language,
+    pub lines: usize,
+}
+
+/// Returns the first packages found under `dir` along with the number of packages left out
+pub fn get_packages(dir: &str, ignored_directories: &[String]) -> (Vec<Package>, usize) {
+    let roots = find_package_roots(Path::new(dir), ignored_directories);
+    let remaining = roots.len().saturating_sub(MAX_PACKAGES);
+
+    let packages = roots
+        .into_iter()
+        .take(MAX_PACKAGES)
+        .map(|root| {
+            let ignored: Vec<&str> = ignored_directories.iter().map(|s| s.as_str()).collect();
+            let (language, lines) =
+                match Language::get_language_stats(&root.to_string_lossy(), ignored) {
+                    Ok((stats, lines, _)) => (stats[0].0.clone(), lines),
+                    Err(_) => (Language::Unknown, 0),
+                };
+            let path = root
+                .strip_prefix(dir)
+                .unwrap_or(&root)
+                .to_string_lossy()
+                .into_owned();
+            Package {
+                path,
+                language,
+                lines,
+            }
+        })
+        .collect();
+
+    (packages, remaining)
+}
+
+/// Returns the member directories of the Cargo workspace whose manifest is in `dir`, expanding
+/// the globs of `members` and leaving out `exclude` the way Cargo does. `None` when the
+/// manifest doesn't list workspace members.
+fn cargo_workspace_members(dir: &Path) -> Option<Vec<PathBuf>> {
+    let manifest: toml::Value =
+        toml::from_str(&fs::read_to_string(dir.join("Cargo.toml")).ok()?).ok()?;
+    let workspace = manifest.get("workspace")?;
+    let excluded: Vec<PathBuf> = workspace
+        .get("exclude")
+        .and_then(toml::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(toml::Value::as_str)
+        .map(|path| dir.join(path))
+        .collect();
+
+    let mut members = Vec::new();
+    for pattern in workspace.get("members")?.as_array()? {
+        let pattern = dir.join(pattern.as_str()?);
+        for member in glob::glob(&pattern.to_string_lossy()).ok()?.flatten() {
+            if member.join("Cargo.toml").is_file()
+                && !excluded.iter().any(|excluded| member.starts_with(excluded))
+            {
+                members.push(member);
+            }
+        }
+    }
+    Some(members)
+}
+
+fn find_package_roots(dir: &Path, ignored_directories: &[String]) -> Vec<PathBuf> {
+    // In a Cargo workspace, only its members are Cargo packages, whatever other manifests
+    // (examples, fixtures, ...) the tree holds
+    let workspace_members = cargo_workspace_members(dir);
+    let is_workspace = workspace_members.is_some();
+
+    let mut overrides = OverrideBuilder::new(dir);
+    for ignored in ignored_directories {
+        let _ = overrides.add(&format!("!{}", ignored));
+    }
+    let overrides = overrides.build().ok();
+
+    let mut walker = WalkBuilder::new(dir);
+    walker.max_depth(Some(MAX_DEPTH));
+    if let Some(overrides) = &overrides {
+        walker.overrides(overrides.clone());
+    }
+    let workspace_members =
+        workspace_members
+            .into_iter()
+            .flatten()
+            .filter(|member| match &overrides {
+                Some(overrides) => !overrides.matched(member, true).is_ignore(),
+                None => true,
+            });
+
+    let mut roots = walker
+        .build()
+        .filter_map(std::result::Result::ok)
+        .filter(|entry| {
+            entry.path().is_file()
+                && MANIFEST_FILES
+                    .iter()
+                    .filter(|&&manifest| manifest != "Cargo.toml" || !is_workspace)
+                    .any(|&manifest| entry.file_name() == manifest)
+        })
+        .filter_map(|entry| entry.path().parent().map(Path::to_path_buf))
+        .chain(workspace_members)
+        .filter(|root| root != dir)
+        .collect::<Vec<_>>();
+
+    roots.sort();
+    roots.dedup();
+
+    // Nested manifests (examples, fixtures, ...) belong to their enclosing package
+    let mut outermost: Vec<PathBuf> = Vec::new();
+    for root in roots {
+        match outermost.last() {
+            Some(last) if root.starts_with(last) => {}
+            _ => outermost.push(root),
+        }
+    }
+    outermost
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, crate::fixture};
+
+    #[test]
+    fn workspace_members_and_nested_packages_are_found() {
+        let dir = fixture::dir(&[
+            (
+                "Cargo.toml",
+                "[workspace]\nmembers = [\"crates/*\"]\nexclude = [\"crates/legacy\"]\n",
+            ),
+            ("crates/core/Cargo.toml", "[package]\nname = \"core\"\n"),
+            ("crates/core/src/lib.rs", "pub fn run() {}\n"),
+            ("crates/cli/Cargo.toml", "[package]\nname = \"cli\"\n"),
+            ("crates/legacy/Cargo.toml", "[package]\nname = \"legacy\"\n"),
+            ("crates/notes.md", "# Notes\n"),
+            ("examples/demo/Cargo.toml", "[package]\nname = \"demo\"\n"),
+            ("web/app/package.json", "{}\n"),
+        ]);
+
+        let roots = |excluded: &[String]| {
+            find_package_roots(dir.path(), excluded)
+                .into_iter()
+                .map(|root| root.strip_prefix(dir.path()).unwrap().to_path_buf())
+                .collect::<Vec<_>>()
+        };
+        let paths = |paths: &[&str]| paths.iter().map(PathBuf::from).collect::<Vec<_>>();
+        assert_eq!(roots(&[]), paths(&["crates/cli", "crates/core", "web/app"]));
+        assert_eq!(
+            roots(&[String::from("crates/cli")]),
+            paths(&["crates/core", "web/app"])
+        );
+    }
+}
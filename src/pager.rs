@@ -0,0 +1,50 @@
+use std::{
+    env,
+    io::{self, Write},
+    process::{Command, Stdio},
+};
+
+/// Pager used when `$PAGER` isn't set, `-R` keeping the colors
+const DEFAULT_PAGER: &str = "less -R";
+/// Options given to less through `$LESS` when unset: quit when the output fits on one screen,
+/// keep the colors and don't clear the screen on exit
+const DEFAULT_LESS_OPTIONS: &str = "FRX";
+
+/// Writes the output through `$PAGER`, or `less -R` by default.
+/// Returns false, leaving the output to the caller, when stdout isn't a terminal
+/// or the pager couldn't be started.
+pub fn page(output: &str) -> bool {
+    if !atty::is(atty::Stream::Stdout) {
+        return false;
+    }
+
+    let pager = env::var("PAGER")
+        .ok()
+        .filter(|pager| !pager.trim().is_empty())
+        .unwrap_or_else(|| String::from(DEFAULT_PAGER));
+    let mut words = pager.split_whitespace();
+    let program = match words.next() {
+        Some(program) => program,
+        None => return false,
+    };
+
+    let mut command = Command::new(program);
+    command.args(words).stdin(Stdio::piped());
+    if env::var_os("LESS").is_none() {
+        command.env("LESS", DEFAULT_LESS_OPTIONS);
+    }
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(_) => return false,
+    };
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // The pager may be quit before reading everything
+        match stdin.write_all(output.as_bytes()) {
+            Ok(()) => {}
+            Err(error) if error.kind() == io::ErrorKind::BrokenPipe => {}
+            Err(_) => return false,
+        }
+    }
+    child.wait().is_ok()
+}
@@ -0,0 +1,483 @@
+//! Minimal QR code encoder: byte mode, error correction level L, versions 1 to 6.
+//! That is enough for repository URLs of up to 134 bytes, longer ones aren't rendered.
+
+/// (data codewords per block, number of blocks, ec codewords per block) of versions 1 to 6
+const VERSIONS: [(usize, usize, usize); 6] = [
+    (19, 1, 7),
+    (34, 1, 10),
+    (55, 1, 15),
+    (80, 1, 20),
+    (108, 1, 26),
+    (68, 2, 18),
+];
+/// Light modules around the code, as required by the standard
+const QUIET_ZONE: usize = 4;
+
+pub struct QrCode {
+    size: usize,
+    modules: Vec<Vec<bool>>,
+}
+
+impl QrCode {
+    /// Encodes the text in the smallest version that fits it, if any
+    pub fn new(text: &str) -> Option<Self> {
+        let bytes = text.as_bytes();
+        let version = VERSIONS
+            .iter()
+            .position(|(data, blocks, _)| 2 + bytes.len() <= data * blocks)?
+            + 1;
+        let (data_len, blocks, ec_len) = VERSIONS[version - 1];
+
+        let codewords = interleave(&encode_data(bytes, data_len * blocks), blocks, ec_len);
+
+        let size = 17 + 4 * version;
+        let mut qr = QrCode {
+            size,
+            modules: vec![vec![false; size]; size],
+        };
+        let mut is_function = vec![vec![false; size]; size];
+        qr.draw_function_patterns(version, &mut is_function);
+        qr.draw_codewords(&codewords, &is_function);
+
+        let mask = (0..8)
+            .min_by_key(|&mask| {
+                let mut candidate = QrCode {
+                    size,
+                    modules: qr.modules.clone(),
+                };
+                candidate.apply_mask(mask, &is_function);
+                candidate.draw_format_bits(mask, &mut is_function.clone());
+                candidate.penalty()
+            })
+            .unwrap_or(0);
+        qr.apply_mask(mask, &is_function);
+        qr.draw_format_bits(mask, &mut is_function);
+
+        Some(qr)
+    }
+
+    /// Width of the rendered code, in columns
+    pub fn width(&self) -> usize {
+        self.size + 2 * QUIET_ZONE
+    }
+
+    /// Renders the code with half blocks, two modules per character cell.
+    /// Light modules are drawn so the code scans on dark terminal backgrounds.
+    pub fn render(&self) -> String {
+        let is_light = |y: isize, x: isize| {
+            let (y, x) = (y - QUIET_ZONE as isize, x - QUIET_ZONE as isize);
+            y < 0
+                || x < 0
+                || y >= self.size as isize
+                || x >= self.size as isize
+                || !self.modules[y as usize][x as usize]
+        };
+
+        let mut buf = String::new();
+        for y in (0..self.width() as isize).step_by(2) {
+            for x in 0..self.width() as isize {
+                buf.push(match (is_light(y, x), is_light(y + 1, x)) {
+                    (true, true) => '█',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (false, false) => ' ',
+                });
+            }
+            buf.push('\n');
+        }
+        buf
+    }
+
+    /// Renders the code with `#`, one line and two columns per module,
+    /// for terminals without Unicode support
+    pub fn render_ascii(&self) -> String {
+        let quiet_line = format!("{}\n", "##".repeat(self.width()));
+        let quiet_side = "##".repeat(QUIET_ZONE);
+
+        let mut buf = quiet_line.repeat(QUIET_ZONE);
+        for row in &self.modules {
+            buf.push_str(&quiet_side);
+            for dark in row {
+                buf.push_str(if *dark { "  " } else { "##" });
+            }
+            buf.push_str(&quiet_side);
+            buf.push('\n');
+        }
+        buf.push_str(&quiet_line.repeat(QUIET_ZONE));
+        buf
+    }
+
+    fn set_function(&mut self, is_function: &mut [Vec<bool>], x: usize, y: usize, dark: bool) {
+        self.modules[y][x] = dark;
+        is_function[y][x] = true;
+    }
+
+    fn draw_function_patterns(&mut self, version: usize, is_function: &mut [Vec<bool>]) {
+        for i in 0..self.size {
+            self.set_function(is_function, 6, i, i % 2 == 0);
+            self.set_function(is_function, i, 6, i % 2 == 0);
+        }
+
+        let last = self.size - 4;
+        for &(cx, cy) in &[(3, 3), (last, 3), (3, last)] {
+            for dy in -4isize..=4 {
+                for dx in -4isize..=4 {
+                    let (x, y) = (cx as isize + dx, cy as isize + dy);
+                    if x >= 0 && y >= 0 && x < self.size as isize && y < self.size as isize {
+                        let distance = dx.abs().max(dy.abs());
+                        self.set_function(
+                            is_function,
+                            x as usize,
+                            y as usize,
+                            distance != 2 && distance != 4,
+                        );
+                    }
+                }
+            }
+        }
+
+        if version > 1 {
+            let center = self.size as isize - 7;
+            for dy in -2isize..=2 {
+                for dx in -2isize..=2 {
+                    let distance = dx.abs().max(dy.abs());
+                    self.set_function(
+                        is_function,
+                        (center + dx) as usize,
+                        (center + dy) as usize,
+                        distance != 1,
+                    );
+                }
+            }
+        }
+
+        // Reserve the format areas, they are drawn once the mask is chosen
+        self.draw_format_bits(0, is_function);
+    }
+
+    fn draw_format_bits(&mut self, mask: u32, is_function: &mut [Vec<bool>]) {
+        // Error correction level L is 0b01
+        let data = (1 << 3) | mask;
+        let mut remainder = data;
+        for _ in 0..10 {
+            remainder = (remainder << 1) ^ ((remainder >> 9) * 0x537);
+        }
+        let bits = ((data << 10) | remainder) ^ 0x5412;
+        let bit = |i: usize| (bits >> i) & 1 != 0;
+
+        for i in 0..=5 {
+            self.set_function(is_function, 8, i, bit(i));
+        }
+        self.set_function(is_function, 8, 7, bit(6));
+        self.set_function(is_function, 8, 8, bit(7));
+        self.set_function(is_function, 7, 8, bit(8));
+        for i in 9..15 {
+            self.set_function(is_function, 14 - i, 8, bit(i));
+        }
+
+        let size = self.size;
+        for i in 0..8 {
+            self.set_function(is_function, size - 1 - i, 8, bit(i));
+        }
+        for i in 8..15 {
+            self.set_function(is_function, 8, size - 15 + i, bit(i));
+        }
+        self.set_function(is_function, 8, size - 8, true);
+    }
+
+    /// Places the codewords in the zigzag order, two columns at a time from the bottom right
+    fn draw_codewords(&mut self, codewords: &[u8], is_function: &[Vec<bool>]) {
+        let mut i = 0;
+        let mut right = self.size - 1;
+        while right >= 1 {
+            if right == 6 {
+                right = 5;
+            }
+            let upward = (right + 1) & 2 == 0;
+            for vertical in 0..self.size {
+                let y = if upward {
+                    self.size - 1 - vertical
+                } else {
+                    vertical
+                };
+                for x in &[right, right - 1] {
+                    if !is_function[y][*x] && i < codewords.len() * 8 {
+                        self.modules[y][*x] = (codewords[i / 8] >> (7 - i % 8)) & 1 != 0;
+                        i += 1;
+                    }
+                }
+            }
+            if right < 2 {
+                break;
+            }
+            right -= 2;
+        }
+    }
+
+    fn apply_mask(&mut self, mask: u32, is_function: &[Vec<bool>]) {
+        for (y, row) in self.modules.iter_mut().enumerate() {
+            for (x, module) in row.iter_mut().enumerate() {
+                let invert = match mask {
+                    0 => (x + y) % 2 == 0,
+                    1 => y % 2 == 0,
+                    2 => x % 3 == 0,
+                    3 => (x + y) % 3 == 0,
+                    4 => (x / 3 + y / 2) % 2 == 0,
+                    5 => x * y % 2 + x * y % 3 == 0,
+                    6 => (x * y % 2 + x * y % 3) % 2 == 0,
+                    _ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+                };
+                if invert && !is_function[y][x] {
+                    *module = !*module;
+                }
+            }
+        }
+    }
+
+    /// Penalty score of ISO/IEC 18004 section 7.8.3: runs of 5 modules or more, 2x2 blocks,
+    /// finder-like patterns and dark/light imbalance
+    fn penalty(&self) -> usize {
+        let mut penalty = 0;
+        for i in 0..self.size {
+            penalty += self.line_penalty(|j| self.modules[i][j]);
+            penalty += self.line_penalty(|j| self.modules[j][i]);
+        }
+
+        for y in 1..self.size {
+            for x in 1..self.size {
+                let color = self.modules[y][x];
+                if color == self.modules[y - 1][x]
+                    && color == self.modules[y][x - 1]
+                    && color == self.modules[y - 1][x - 1]
+                {
+                    penalty += 3;
+                }
+            }
+        }
+
+        // 10 points per started 5% of deviation from half of the modules being dark, the first
+        // 5% excepted
+        let dark = self.modules.iter().flatten().filter(|dark| **dark).count();
+        let total = self.size * self.size;
+        let deviation = (dark * 20).max(total * 10) - (dark * 20).min(total * 10);
+        penalty + deviation.saturating_sub(1) / total * 10
+    }
+
+    /// Penalty of the runs and of the finder-like patterns of a row or a column, the quiet zone
+    /// around the code counting as light modules
+    fn line_penalty(&self, module: impl Fn(usize) -> bool) -> usize {
+        let mut penalty = 0;
+        // Lengths of the last runs, the latest first, starting with a light one
+        let mut runs = [0; 7];
+        let push = |runs: &mut [usize; 7], run: usize| {
+            // The first run extends into the quiet zone
+            let run = if runs[0] == 0 { run + self.size } else { run };
+            runs.rotate_right(1);
+            runs[0] = run;
+        };
+        let mut color = false;
+        let mut run = 0;
+        for i in 0..self.size {
+            if module(i) == color {
+                run += 1;
+                if run == 5 {
+                    penalty += 3;
+                } else if run > 5 {
+                    penalty += 1;
+                }
+            } else {
+                push(&mut runs, run);
+                if !color {
+                    penalty += 40 * finder_patterns(&runs);
+                }
+                color = !color;
+                run = 1;
+            }
+        }
+
+        if color {
+            push(&mut runs, run);
+            run = 0;
+        }
+        push(&mut runs, run + self.size);
+        penalty + 40 * finder_patterns(&runs)
+    }
+}
+
+/// Counts the dark-light-dark-light-dark runs of ratio 1:1:3:1:1 ending `runs`, the latest run
+/// first, whose light runs on either side are 4 times as long
+fn finder_patterns(runs: &[usize; 7]) -> usize {
+    let n = runs[1];
+    if n == 0 || runs[2] != n || runs[3] != 3 * n || runs[4] != n || runs[5] != n {
+        return 0;
+    }
+    usize::from(runs[0] >= 4 * n && runs[6] >= n) + usize::from(runs[6] >= 4 * n && runs[0] >= n)
+}
+
+/// Byte mode segment followed by the terminator and the padding codewords
+fn encode_data(bytes: &[u8], capacity: usize) -> Vec<u8> {
+    let mut bits: Vec<bool> = Vec::new();
+    push_bits(&mut bits, 0b0100, 4);
+    push_bits(&mut bits, bytes.len(), 8);
+    for byte in bytes {
+        push_bits(&mut bits, usize::from(*byte), 8);
+    }
+    let terminator = (capacity * 8 - bits.len()).min(4);
+    push_bits(&mut bits, 0, terminator);
+    let padding = (8 - bits.len() % 8) % 8;
+    push_bits(&mut bits, 0, padding);
+
+    let mut data: Vec<u8> = bits
+        .chunks(8)
+        .map(|byte| byte.iter().fold(0, |acc, bit| (acc << 1) | u8::from(*bit)))
+        .collect();
+    for pad in [0xEC, 0x11].iter().cycle() {
+        if data.len() == capacity {
+            break;
+        }
+        data.push(*pad);
+    }
+    data
+}
+
+fn push_bits(bits: &mut Vec<bool>, value: usize, length: usize) {
+    for i in (0..length).rev() {
+        bits.push((value >> i) & 1 != 0);
+    }
+}
+
+/// Splits the data into blocks, computes their error correction codewords and interleaves them
+fn interleave(data: &[u8], blocks: usize, ec_len: usize) -> Vec<u8> {
+    let generator = generator_polynomial(ec_len);
+    let data_blocks: Vec<&[u8]> = data.chunks(data.len() / blocks).collect();
+    let ec_blocks: Vec<Vec<u8>> = data_blocks
+        .iter()
+        .map(|block| remainder(block, &generator))
+        .collect();
+
+    let mut codewords = Vec::new();
+    for i in 0..data.len() / blocks {
+        codewords.extend(data_blocks.iter().map(|block| block[i]));
+    }
+    for i in 0..ec_len {
+        codewords.extend(ec_blocks.iter().map(|block| block[i]));
+    }
+    codewords
+}
+
+/// Product of (x - α^i) for i in 0..degree, highest degree coefficient first
+fn generator_polynomial(degree: usize) -> Vec<u8> {
+    let mut generator = vec![1];
+    let mut root = 1;
+    for _ in 0..degree {
+        let mut next = vec![0; generator.len() + 1];
+        for (i, coefficient) in generator.iter().enumerate() {
+            next[i] ^= coefficient;
+            next[i + 1] ^= gf_multiply(*coefficient, root);
+        }
+        generator = next;
+        root = gf_multiply(root, 2);
+    }
+    generator
+}
+
+fn remainder(data: &[u8], generator: &[u8]) -> Vec<u8> {
+    let mut message = data.to_vec();
+    message.resize(data.len() + generator.len() - 1, 0);
+    for i in 0..data.len() {
+        let factor = message[i];
+        if factor != 0 {
+            for (j, coefficient) in generator.iter().enumerate() {
+                message[i + j] ^= gf_multiply(*coefficient, factor);
+            }
+        }
+    }
+    message.split_off(data.len())
+}
+
+/// Multiplication in GF(2^8) modulo x^8 + x^4 + x^3 + x^2 + 1
+fn gf_multiply(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    while b != 0 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        let carry = a & 0x80 != 0;
+        a <<= 1;
+        if carry {
+            a ^= 0x1D;
+        }
+        b >>= 1;
+    }
+    product
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Modules of the code, without the quiet zone, `#` being dark
+    fn modules(qr: &QrCode) -> String {
+        qr.modules
+            .iter()
+            .map(|row| {
+                let mut line: String = row
+                    .iter()
+                    .map(|&dark| if dark { '#' } else { '.' })
+                    .collect();
+                line.push('\n');
+                line
+            })
+            .collect()
+    }
+
+    /// The reference matrices were made by a separate encoder, then decoded back to the URL
+    #[test]
+    fn codes_match_the_reference_matrices() {
+        let codes = [
+            ("https://o2sh.dev", include_str!("../tests/fixtures/qr/v1.txt")),
+            (
+                "https://github.com/o2sh/onefetch",
+                include_str!("../tests/fixtures/qr/v2.txt"),
+            ),
+            (
+                "https://github.com/o2sh/onefetch/tree/main/resources",
+                include_str!("../tests/fixtures/qr/v3.txt"),
+            ),
+            (
+                "https://gitlab.com/gitlab-org/gitlab-runner/-/tree/main/commands/helpers",
+                include_str!("../tests/fixtures/qr/v4.txt"),
+            ),
+            (
+                "https://github.com/o2sh/onefetch/blob/main/src/onefetch/image_backends/kitty.rs#L1-L24",
+                include_str!("../tests/fixtures/qr/v5.txt"),
+            ),
+            (
+                "https://gitlab.freedesktop.org/mesa/mesa/-/tree/main/src/gallium/drivers/radeonsi/ci/deqp-radeonsi-stress.toml",
+                include_str!("../tests/fixtures/qr/v6.txt"),
+            ),
+        ];
+        for (version, (url, reference)) in codes.iter().enumerate() {
+            let qr = QrCode::new(url).unwrap();
+            assert_eq!(qr.size, 17 + 4 * (version + 1), "{}", url);
+            assert_eq!(modules(&qr), *reference, "{}", url);
+        }
+    }
+
+    #[test]
+    fn urls_longer_than_version_6_are_not_encoded() {
+        assert!(QrCode::new(&"a".repeat(134)).is_some());
+        assert!(QrCode::new(&"a".repeat(135)).is_none());
+    }
+
+    #[test]
+    fn quiet_zone_surrounds_the_code() {
+        let qr = QrCode::new("https://o2sh.dev").unwrap();
+        let rendered = qr.render_ascii();
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines.len(), 21 + 2 * QUIET_ZONE);
+        assert!(lines[..QUIET_ZONE].iter().all(|line| !line.contains(' ')));
+        assert!(lines[QUIET_ZONE].starts_with(&"##".repeat(QUIET_ZONE)));
+        assert_eq!(&lines[QUIET_ZONE][2 * QUIET_ZONE..2 * QUIET_ZONE + 2], "  ");
+    }
+}
@@ -0,0 +1,229 @@
+use git2::{Oid, Repository};
+
+const PGP_SIGNATURE_HEADER: &str = "-----BEGIN PGP SIGNATURE-----";
+const SSH_SIGNATURE_HEADER: &str = "-----BEGIN SSH SIGNATURE-----";
+const SSH_SIGNATURE_MAGIC: &[u8] = b"SSHSIG";
+const PGP_SIGNATURE_PACKET: u8 = 2;
+const PGP_ISSUER_SUBPACKET: u8 = 16;
+const PGP_SIGNER_USER_ID_SUBPACKET: u8 = 28;
+const PGP_ISSUER_FINGERPRINT_SUBPACKET: u8 = 33;
+
+/// Describes who signed HEAD: the key ID of a PGP signature, along with the signer's user ID
+/// when the signature carries it, or the key type of an SSH signature.
+/// Returns `unsigned` when HEAD has no signature and `None` when there is no HEAD commit.
+pub fn get_head_signer(repo: &Repository) -> Option<String> {
+    let head = repo.head().ok()?.peel_to_commit().ok()?;
+    let signature = match repo.extract_signature(&head.id(), None) {
+        Ok((signature, _)) => signature,
+        Err(_) => return Some(String::from("unsigned")),
+    };
+    let signature = signature.as_str().unwrap_or_default();
+    Some(describe_signer(signature).unwrap_or_else(|| String::from("unknown key")))
+}
+
+/// Whether the commit carries a signature, whoever made it
+pub fn is_signed(repo: &Repository, oid: Oid) -> bool {
+    repo.extract_signature(&oid, None).is_ok()
+}
+
+fn describe_signer(signature: &str) -> Option<String> {
+    if signature.contains(PGP_SIGNATURE_HEADER) {
+        let packet = decode_armor(signature, PGP_SIGNATURE_HEADER)?;
+        let (key_id, user_id) = parse_pgp_signature(&packet)?;
+        match user_id {
+            Some(user_id) => Some(format!("{} ({})", user_id, key_id)),
+            None => Some(key_id),
+        }
+    } else if signature.contains(SSH_SIGNATURE_HEADER) {
+        let blob = decode_armor(signature, SSH_SIGNATURE_HEADER)?;
+        parse_ssh_key_type(&blob).map(|key_type| format!("SSH key ({})", key_type))
+    } else {
+        None
+    }
+}
+
+/// Decodes the base64 body of an ASCII-armored block, skipping the armor headers and checksum
+fn decode_armor(armored: &str, header: &str) -> Option<Vec<u8>> {
+    let mut lines = armored
+        .lines()
+        .map(str::trim)
+        .skip_while(|line| *line != header)
+        .skip(1)
+        .take_while(|line| !line.starts_with("-----END"))
+        .peekable();
+    if header == PGP_SIGNATURE_HEADER {
+        while let Some(line) = lines.peek() {
+            if !line.contains(": ") {
+                break;
+            }
+            lines.next();
+        }
+    }
+    let body: String = lines
+        .filter(|line| !line.is_empty() && !line.starts_with('='))
+        .collect();
+    base64::decode(body).ok()
+}
+
+/// Reads the issuer key ID, as 16 uppercase hexadecimal digits, and the signer's user ID
+/// from an OpenPGP signature packet
+fn parse_pgp_signature(packet: &[u8]) -> Option<(String, Option<String>)> {
+    let (tag, body) = read_pgp_packet(packet)?;
+    if tag != PGP_SIGNATURE_PACKET {
+        return None;
+    }
+
+    let mut key_id = None;
+    let mut user_id = None;
+    match *body.first()? {
+        3 => key_id = body.get(7..15).map(<[u8]>::to_vec),
+        version @ 4..=6 => {
+            let length_size = if version == 6 { 4 } else { 2 };
+            let mut offset = 4;
+            for _ in 0..2 {
+                let length = read_be(body.get(offset..offset + length_size)?);
+                offset += length_size;
+                let subpackets = body.get(offset..offset + length)?;
+                offset += length;
+                for (kind, data) in SubpacketIter(subpackets) {
+                    match kind {
+                        PGP_ISSUER_SUBPACKET if key_id.is_none() => key_id = Some(data.to_vec()),
+                        PGP_ISSUER_FINGERPRINT_SUBPACKET if data.len() > 8 => {
+                            key_id = Some(data[data.len() - 8..].to_vec())
+                        }
+                        PGP_SIGNER_USER_ID_SUBPACKET => {
+                            user_id = Some(String::from_utf8_lossy(data).into_owned())
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+        _ => return None,
+    }
+
+    let key_id = key_id?.iter().map(|byte| format!("{:02X}", byte)).collect();
+    Some((key_id, user_id))
+}
+
+/// Splits the first packet of an OpenPGP message into its tag and its body
+fn read_pgp_packet(packet: &[u8]) -> Option<(u8, &[u8])> {
+    let header = *packet.first()?;
+    if header & 0x80 == 0 {
+        return None;
+    }
+    if header & 0x40 != 0 {
+        let (length, offset) = read_pgp_length(&packet[1..])?;
+        Some((header & 0x3f, packet.get(1 + offset..1 + offset + length)?))
+    } else {
+        let length_size = match header & 0x03 {
+            0 => 1,
+            1 => 2,
+            2 => 4,
+            _ => return Some(((header >> 2) & 0x0f, &packet[1..])),
+        };
+        let length = read_be(packet.get(1..1 + length_size)?);
+        let start = 1 + length_size;
+        Some(((header >> 2) & 0x0f, packet.get(start..start + length)?))
+    }
+}
+
+/// Reads a new-format packet or subpacket length, returning it with the number of bytes used
+fn read_pgp_length(bytes: &[u8]) -> Option<(usize, usize)> {
+    match *bytes.first()? {
+        first @ 0..=191 => Some((first as usize, 1)),
+        first @ 192..=254 => {
+            let second = *bytes.get(1)? as usize;
+            Some((((first as usize - 192) << 8) + second + 192, 2))
+        }
+        255 => Some((read_be(bytes.get(1..5)?), 5)),
+    }
+}
+
+fn read_be(bytes: &[u8]) -> usize {
+    bytes
+        .iter()
+        .fold(0, |value, &byte| (value << 8) | byte as usize)
+}
+
+/// Iterates over the type and data of the subpackets of a signature packet
+struct SubpacketIter<'a>(&'a [u8]);
+
+impl<'a> Iterator for SubpacketIter<'a> {
+    type Item = (u8, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (length, offset) = read_pgp_length(self.0)?;
+        let subpacket = self.0.get(offset..offset + length)?;
+        self.0 = &self.0[offset + length..];
+        let (&kind, data) = subpacket.split_first()?;
+        Some((kind & 0x7f, data))
+    }
+}
+
+/// Reads the type of the public key embedded in an SSH signature blob
+fn parse_ssh_key_type(blob: &[u8]) -> Option<String> {
+    let rest = blob.strip_prefix(SSH_SIGNATURE_MAGIC)?.get(4..)?;
+    let public_key = read_ssh_string(rest)?;
+    let key_type = read_ssh_string(public_key)?;
+    String::from_utf8(key_type.to_vec()).ok()
+}
+
+fn read_ssh_string(bytes: &[u8]) -> Option<&[u8]> {
+    let length = read_be(bytes.get(0..4)?);
+    bytes.get(4..4 + length)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn armor(header: &str, footer: &str, body: &[u8]) -> String {
+        format!(
+            "{}\n\n{}\n=AbCd\n{}\n",
+            header,
+            base64::encode(body),
+            footer
+        )
+    }
+
+    #[test]
+    fn pgp_signatures_report_the_issuer_key_id() {
+        let fingerprint: Vec<u8> = (1..=20).collect();
+        let mut hashed = vec![22, PGP_ISSUER_FINGERPRINT_SUBPACKET, 4];
+        hashed.extend(&fingerprint);
+        let mut body = vec![4, 0, 1, 8, 0, hashed.len() as u8];
+        body.extend(&hashed);
+        body.extend(&[0, 0, 0xab, 0xcd]);
+        let mut packet = vec![0xc2, body.len() as u8];
+        packet.extend(&body);
+
+        let signature = armor(PGP_SIGNATURE_HEADER, "-----END PGP SIGNATURE-----", &packet);
+        assert_eq!(
+            describe_signer(&signature),
+            Some(String::from("0D0E0F1011121314"))
+        );
+    }
+
+    #[test]
+    fn ssh_signatures_report_the_key_type() {
+        let key_type = b"ssh-ed25519";
+        let mut public_key = (key_type.len() as u32).to_be_bytes().to_vec();
+        public_key.extend(key_type);
+        let mut blob = SSH_SIGNATURE_MAGIC.to_vec();
+        blob.extend(&1u32.to_be_bytes());
+        blob.extend(&(public_key.len() as u32).to_be_bytes());
+        blob.extend(&public_key);
+
+        let signature = armor(SSH_SIGNATURE_HEADER, "-----END SSH SIGNATURE-----", &blob);
+        assert_eq!(
+            describe_signer(&signature),
+            Some(String::from("SSH key (ssh-ed25519)"))
+        );
+    }
+
+    #[test]
+    fn unknown_signatures_are_not_described() {
+        assert_eq!(describe_signer("not a signature"), None);
+    }
+}
@@ -0,0 +1,105 @@
+use {
+    crate::language::{Language, LanguageStats},
+    git2::{Repository, StatusOptions},
+    serde::{Deserialize, Serialize},
+    std::fs,
+};
+
+/// File of the git directory holding the language stats of the last run with `--cache`
+const CACHE_FILE: &str = "onefetch-languages.json";
+
+#[derive(Serialize, Deserialize)]
+struct CachedStats {
+    key: String,
+    languages: Vec<(Language, f64, usize)>,
+    lines: usize,
+    presence: Vec<(Language, (usize, usize, usize))>,
+}
+
+/// Returns what the language stats depend on: the version of onefetch, the commit of HEAD,
+/// the excluded patterns and whether only the committed files are counted.
+/// `None` without any commit, or when the working tree, counted instead of the committed files,
+/// has changes the commit doesn't hold.
+pub fn key(repo: &Repository, excluded: &[String], committed_only: bool) -> Option<String> {
+    let head = repo.head().ok()?.target()?;
+    if !committed_only {
+        let mut options = StatusOptions::new();
+        options.include_untracked(true).include_ignored(false);
+        if !repo.statuses(Some(&mut options)).ok()?.is_empty() {
+            return None;
+        }
+    }
+    Some(format!(
+        "{} {} {} {}",
+        env!("CARGO_PKG_VERSION"),
+        head,
+        committed_only,
+        excluded.join(",")
+    ))
+}
+
+/// Returns the stats saved under the same key, `None` when there are none or the key differs
+pub fn load(repo: &Repository, key: &str) -> Option<LanguageStats> {
+    let content = fs::read_to_string(repo.path().join(CACHE_FILE)).ok()?;
+    let cached: CachedStats = serde_json::from_str(&content).ok()?;
+    if cached.key != key {
+        return None;
+    }
+    Some((
+        cached.languages,
+        cached.lines,
+        cached.presence.into_iter().collect(),
+    ))
+}
+
+/// Saves the stats under the key, replacing those of a previous run. Failing to write the
+/// cache only means the stats are computed again next time.
+pub fn save(repo: &Repository, key: &str, stats: &LanguageStats) {
+    let (languages, lines, presence) = stats;
+    let cached = CachedStats {
+        key: key.to_string(),
+        languages: languages.clone(),
+        lines: *lines,
+        presence: presence
+            .iter()
+            .map(|(language, counts)| (language.clone(), *counts))
+            .collect(),
+    };
+    if let Ok(content) = serde_json::to_string(&cached) {
+        let _ = fs::write(repo.path().join(CACHE_FILE), content);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, crate::fixture};
+
+    #[test]
+    fn cached_stats_are_dropped_once_head_moves_or_the_tree_changes() {
+        let (dir, repo) = fixture::repo(&[("main.rs", "fn main() {}\n")]);
+        assert_eq!(key(&repo, &[], true), None);
+        fixture::commit(&repo, "onefetch", "first");
+
+        let stats: LanguageStats = (
+            vec![(Language::Rust, 100.0, 1)],
+            1,
+            vec![(Language::Rust, (1, 1, 0))].into_iter().collect(),
+        );
+        let saved_key = key(&repo, &[], false).unwrap();
+        save(&repo, &saved_key, &stats);
+        let (languages, lines, presence) = load(&repo, &saved_key).unwrap();
+        assert!(languages == stats.0 && lines == 1 && presence == stats.2);
+        assert!(load(&repo, &key(&repo, &[String::from("*.rs")], false).unwrap()).is_none());
+
+        fs::write(dir.path().join("lib.rs"), "pub fn run() {}\n").unwrap();
+        assert_eq!(key(&repo, &[], false), None);
+        assert_eq!(
+            key(&repo, &[], true).as_deref().map(|key| key == saved_key),
+            Some(false)
+        );
+
+        fs::remove_file(dir.path().join("lib.rs")).unwrap();
+        fixture::commit(&repo, "onefetch", "second");
+        assert!(load(&repo, &key(&repo, &[], false).unwrap()).is_none());
+    }
+}
@@ -0,0 +1,229 @@
+use {
+    crate::{
+        info::Info,
+        info_field::{InfoFields, LABELED_FIELDS},
+        language::Language,
+        Error, Result,
+    },
+    colored::Color,
+    serde::Deserialize,
+    std::{collections::HashMap, fs, path::Path, str::FromStr},
+};
+
+/// Color scheme loaded with `--theme-file`, the command line flags take precedence over it.
+///
+/// ```toml
+/// logo_colors = [4, "#ff8800"]
+/// label_color = 4
+/// value_color = 7
+/// color_blocks = [0, 1, 2, 3, 4, 5, 6, 7]
+/// separator = "="
+/// bold = false
+///
+/// [labels]
+/// lines_of_code = "LOC:"
+///
+/// [colors]
+/// rust = [9, 15]
+/// ```
+///
+/// Colors are either terminal color indices, from 0 to 15, or `#rrggbb` hex codes.
+/// Labels are keyed by the field names of `--disable-fields`, `git_info` excepted.
+/// The `colors` table replaces `logo_colors` for the logos of the given languages,
+/// keyed by the names of `--ascii-language`.
+#[derive(Default)]
+pub struct Theme {
+    pub logo_colors: Vec<Color>,
+    pub label_color: Option<Color>,
+    pub value_color: Option<Color>,
+    pub color_blocks: Option<Vec<Color>>,
+    pub separator: Option<char>,
+    pub bold: Option<bool>,
+    pub labels: HashMap<String, String>,
+    pub language_colors: HashMap<Language, Vec<Color>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeFile {
+    #[serde(default)]
+    logo_colors: Vec<ThemeColor>,
+    label_color: Option<ThemeColor>,
+    value_color: Option<ThemeColor>,
+    color_blocks: Option<Vec<ThemeColor>>,
+    separator: Option<char>,
+    bold: Option<bool>,
+    #[serde(default)]
+    labels: HashMap<String, String>,
+    #[serde(default)]
+    colors: HashMap<String, Vec<ThemeColor>>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ThemeColor {
+    Index(u8),
+    Hex(String),
+}
+
+impl Theme {
+    pub fn from_file(path: &Path) -> Result<Theme> {
+        let content = fs::read_to_string(path)
+            .map_err(|error| Error::ThemeFile(format!("{}: {}", path.display(), error)))?;
+        let file: ThemeFile = toml::from_str(&content)
+            .map_err(|error| Error::ThemeFile(format!("{}: {}", path.display(), error)))?;
+
+        let to_colors = |colors: Vec<ThemeColor>| -> Result<Vec<Color>> {
+            colors.into_iter().map(ThemeColor::into_color).collect()
+        };
+
+        for field in file.labels.keys() {
+            match InfoFields::from_str(field) {
+                Ok(field) if LABELED_FIELDS.contains(&field) => {}
+                _ => {
+                    return Err(Error::ThemeFile(format!(
+                        "{}: unknown label \"{}\"",
+                        path.display(),
+                        field
+                    )))
+                }
+            }
+        }
+
+        let mut language_colors = HashMap::new();
+        for (name, colors) in file.colors {
+            let language = Language::from_str(&name.to_lowercase())
+                .ok()
+                .filter(|language| *language != Language::Unknown)
+                .ok_or_else(|| {
+                    Error::ThemeFile(format!("{}: unknown language \"{}\"", path.display(), name))
+                })?;
+            language_colors.insert(language, to_colors(colors)?);
+        }
+
+        Ok(Theme {
+            logo_colors: to_colors(file.logo_colors)?,
+            label_color: file.label_color.map(ThemeColor::into_color).transpose()?,
+            value_color: file.value_color.map(ThemeColor::into_color).transpose()?,
+            color_blocks: file.color_blocks.map(to_colors).transpose()?,
+            separator: file.separator,
+            bold: file.bold,
+            labels: file.labels,
+            language_colors,
+        })
+    }
+
+    /// Returns the logo colors of the language, falling back to `logo_colors`
+    pub fn logo_colors_for(&self, language: &Language) -> &[Color] {
+        self.language_colors
+            .get(language)
+            .unwrap_or(&self.logo_colors)
+    }
+}
+
+impl ThemeColor {
+    fn into_color(self) -> Result<Color> {
+        match self {
+            ThemeColor::Index(index) => Info::num_to_color(&index.to_string())
+                .ok_or_else(|| Error::ThemeFile(format!("invalid color index {}", index))),
+            ThemeColor::Hex(hex) => parse_hex(&hex)
+                .ok_or_else(|| Error::ThemeFile(format!("invalid hex color \"{}\"", hex))),
+        }
+    }
+}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    let hex = hex.strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+    Some(Color::TrueColor {
+        r: channel(0)?,
+        g: channel(2)?,
+        b: channel(4)?,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use {super::*, crate::fixture};
+
+    fn load(content: &str) -> Result<Theme> {
+        let dir = fixture::dir(&[("theme.toml", content)]);
+        Theme::from_file(&dir.path().join("theme.toml"))
+    }
+
+    fn error(content: &str) -> String {
+        match load(content) {
+            Err(Error::ThemeFile(message)) => message,
+            Err(error) => panic!("unexpected error: {:?}", error),
+            Ok(_) => panic!("the theme was accepted"),
+        }
+    }
+
+    #[test]
+    fn theme_file_is_loaded() {
+        let theme = load(
+            "logo_colors = [4, \"#ff8800\"]\nvalue_color = 15\n\n\
+             [labels]\nlines_of_code = \"LOC:\"\nstars = \"★\"\n\n[colors]\nRust = [9]\n",
+        )
+        .unwrap();
+        assert_eq!(
+            theme.logo_colors,
+            [
+                Color::Blue,
+                Color::TrueColor {
+                    r: 0xff,
+                    g: 0x88,
+                    b: 0x00
+                }
+            ]
+        );
+        assert_eq!(theme.value_color, Some(Color::BrightWhite));
+        assert_eq!(theme.labels["lines_of_code"], "LOC:");
+        assert_eq!(theme.labels["stars"], "★");
+        assert_eq!(theme.logo_colors_for(&Language::Rust), [Color::BrightRed]);
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        assert!(error("logo_color = 4\n").contains("unknown field `logo_color`"));
+    }
+
+    #[test]
+    fn unknown_labels_are_rejected() {
+        assert!(error("[labels]\nlines = \"LOC:\"\n").ends_with("unknown label \"lines\""));
+        assert!(error("[labels]\ngit_info = \"Git:\"\n").ends_with("unknown label \"git_info\""));
+    }
+
+    #[test]
+    fn unknown_languages_are_rejected() {
+        assert!(error("[colors]\nklingon = [1]\n").ends_with("unknown language \"klingon\""));
+    }
+
+    #[test]
+    fn invalid_hex_colors_are_rejected() {
+        assert_eq!(
+            error("label_color = \"#12345g\"\n"),
+            "invalid hex color \"#12345g\""
+        );
+        assert_eq!(
+            error("label_color = \"#fff\"\n"),
+            "invalid hex color \"#fff\""
+        );
+        assert_eq!(
+            error("label_color = \"ff8800\"\n"),
+            "invalid hex color \"ff8800\""
+        );
+    }
+
+    #[test]
+    fn color_indices_above_15_are_rejected() {
+        assert_eq!(error("value_color = 16\n"), "invalid color index 16");
+        assert_eq!(
+            error("color_blocks = [0, 255]\n"),
+            "invalid color index 255"
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use {
+    crate::{Error, Result},
+    git2::{ObjectType, Oid, Repository, TreeWalkMode, TreeWalkResult},
+    ignore::{
+        gitignore::{Gitignore, GitignoreBuilder},
+        WalkBuilder,
+    },
+    std::{
+        collections::HashSet,
+        fs,
+        path::{Path, PathBuf},
+    },
+};
+
+/// Directories holding test files, whatever their name
+const TEST_DIRECTORIES: [&str; 6] = ["test", "tests", "spec", "specs", "__tests__", "testdata"];
+
+/// Prefixes and suffixes of the names of test files, by language
+const TEST_FILE_PATTERNS: [(&str, &str); 14] = [
+    // Go
+    ("", "_test.go"),
+    // Python
+    ("test_", ".py"),
+    ("", "_test.py"),
+    // JavaScript / TypeScript
+    ("", ".spec.js"),
+    ("", ".test.js"),
+    ("", ".spec.ts"),
+    ("", ".test.ts"),
+    ("", ".spec.tsx"),
+    ("", ".test.tsx"),
+    // Java / Kotlin
+    ("", "Test.java"),
+    ("", "Test.kt"),
+    // Ruby
+    ("", "_spec.rb"),
+    // Rust
+    ("", "_test.rs"),
+    // C / C++
+    ("test_", ".c"),
+];
+
+/// Directories holding documentation, whatever the format of the files
+const DOC_DIRECTORIES: [&str; 3] = ["doc", "docs", "documentation"];
+
+/// Extensions of documentation formats: Markdown, reStructuredText, AsciiDoc, Org and TeX
+const DOC_EXTENSIONS: [&str; 7] = ["md", "markdown", "rst", "adoc", "asciidoc", "org", "tex"];
+
+/// Prefix of the names of the readme files, whatever their extension
+const README_PREFIX: &str = "readme";
+
+/// Returns the paths, relative to the workdir, of the files in the index
+/// which don't match any of the excluded patterns
+pub fn get_tracked_files(repo: &Repository, excluded: &[String]) -> Result<Vec<PathBuf>> {
+    let index = repo.index().map_err(|_| Error::ReadIndex)?;
+
+    let matcher = build_exclude_matcher(excluded)?;
+
+    let files = index
+        .iter()
+        .map(|entry| PathBuf::from(String::from_utf8_lossy(&entry.path).into_owned()))
+        .filter(|path| !matcher.matched_path_or_any_parents(path, false).is_ignore())
+        .collect();
+
+    Ok(files)
+}
+
+/// Returns the paths, relative to the workdir, and the ids of the blobs of the tree of HEAD
+/// which don't match any of the excluded patterns
+pub fn get_head_blobs(repo: &Repository, excluded: &[String]) -> Result<Vec<(PathBuf, Oid)>> {
+    let tree = repo
+        .head()
+        .and_then(|head| head.peel_to_tree())
+        .map_err(|_| Error::ReferenceInfoError)?;
+    let matcher = build_exclude_matcher(excluded)?;
+
+    let mut blobs = Vec::new();
+    tree.walk(TreeWalkMode::PreOrder, |dir, entry| {
+        if entry.kind() == Some(ObjectType::Blob) {
+            let path = Path::new(dir).join(entry.name().unwrap_or_default());
+            if !matcher
+                .matched_path_or_any_parents(&path, false)
+                .is_ignore()
+            {
+                blobs.push((path, entry.id()));
+            }
+        }
+        TreeWalkResult::Ok
+    })
+    .map_err(|_| Error::ReferenceInfoError)?;
+
+    Ok(blobs)
+}
+
+/// Returns the paths, relative to `dir`, of the files below a plain directory
+/// which don't match any of the excluded patterns
+pub fn get_directory_files(dir: &Path, excluded: &[String]) -> Result<Vec<PathBuf>> {
+    fn walk(
+        dir: &Path,
+        relative: &Path,
+        matcher: &Gitignore,
+        files: &mut Vec<PathBuf>,
+    ) -> Result<()> {
+        for entry in fs::read_dir(dir).map_err(|_| Error::ReadDirectory)? {
+            let entry = entry.map_err(|_| Error::ReadDirectory)?;
+            let file_type = entry.file_type().map_err(|_| Error::ReadDirectory)?;
+            let path = relative.join(entry.file_name());
+            if matcher.matched(&path, file_type.is_dir()).is_ignore() {
+                continue;
+            }
+            if file_type.is_dir() {
+                walk(&entry.path(), &path, matcher, files)?;
+            } else if file_type.is_file() {
+                files.push(path);
+            }
+        }
+        Ok(())
+    }
+
+    let matcher = build_exclude_matcher(excluded)?;
+    let mut files = Vec::new();
+    walk(dir, Path::new(""), &matcher, &mut files)?;
+    Ok(files)
+}
+
+/// Returns the paths, relative to the workdir, of the files of the working tree which are
+/// neither among the `tracked` files nor ignored by git, leaving out the excluded patterns
+/// and the content of the submodules
+pub fn get_untracked_files(
+    workdir: &Path,
+    tracked: &[PathBuf],
+    excluded: &[String],
+) -> Result<Vec<PathBuf>> {
+    let tracked: HashSet<&Path> = tracked.iter().map(PathBuf::as_path).collect();
+    let matcher = build_exclude_matcher(excluded)?;
+
+    let mut walker = WalkBuilder::new(workdir);
+    walker
+        .hidden(false)
+        .filter_entry(|entry| entry.file_name() != ".git");
+
+    let files = walker
+        .build()
+        .filter_map(std::result::Result::ok)
+        .filter(|entry| entry.file_type().filter(|kind| kind.is_file()).is_some())
+        .filter_map(|entry| {
+            entry
+                .path()
+                .strip_prefix(workdir)
+                .ok()
+                .map(Path::to_path_buf)
+        })
+        .filter(|path| {
+            // A tracked ancestor is a submodule, whose files belong to its own repository
+            !path.ancestors().any(|ancestor| tracked.contains(ancestor))
+                && !matcher.matched_path_or_any_parents(path, false).is_ignore()
+        })
+        .collect();
+
+    Ok(files)
+}
+
+pub fn build_exclude_matcher(excluded: &[String]) -> Result<Gitignore> {
+    let mut builder = GitignoreBuilder::new("");
+    for pattern in excluded {
+        let _ = builder.add_line(None, pattern);
+    }
+    builder.build().map_err(|_| Error::ReadIndex)
+}
+
+/// Whether the file is a test, judging by its directory or its name
+pub fn is_test_file(path: &Path) -> bool {
+    let in_test_directory = path
+        .parent()
+        .into_iter()
+        .flat_map(Path::components)
+        .any(|component| {
+            TEST_DIRECTORIES.contains(&component.as_os_str().to_string_lossy().as_ref())
+        });
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+
+    in_test_directory
+        || TEST_FILE_PATTERNS
+            .iter()
+            .any(|(prefix, suffix)| file_name.starts_with(prefix) && file_name.ends_with(suffix))
+}
+
+/// Whether the file is documentation, judging by its directory, its extension or its name
+pub fn is_doc_file(path: &Path) -> bool {
+    let in_doc_directory = path
+        .parent()
+        .into_iter()
+        .flat_map(Path::components)
+        .any(|component| {
+            DOC_DIRECTORIES.contains(
+                &component
+                    .as_os_str()
+                    .to_string_lossy()
+                    .to_lowercase()
+                    .as_str(),
+            )
+        });
+    let is_doc_extension = path
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+        .filter(|extension| DOC_EXTENSIONS.contains(&extension.as_str()))
+        .is_some();
+    let is_readme = path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_lowercase()
+        .starts_with(README_PREFIX);
+
+    in_doc_directory || is_doc_extension || is_readme
+}
@@ -0,0 +1,92 @@
+use {
+    crate::{tracked_files, Result},
+    std::{
+        collections::hash_map::DefaultHasher,
+        fs,
+        hash::{Hash, Hasher},
+        io::{self, Write},
+        path::{Path, PathBuf},
+        thread,
+        time::{Duration, Instant},
+    },
+};
+
+/// How often the repository is checked for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// Shortest time between two renders, so that a burst of changes renders once
+const MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+/// Files of the git directory touched by commits, checkouts, fetches and staging
+const GIT_FILES: [&str; 5] = ["HEAD", "index", "packed-refs", "FETCH_HEAD", "logs/HEAD"];
+
+/// Renders the info, then renders it again on a cleared screen whenever the git directory,
+/// or the working tree when given along with its exclusions, changes. Stops on Ctrl-C.
+pub fn watch(
+    render: impl Fn() -> Result<String>,
+    git_dir: &Path,
+    worktree: Option<(&Path, &[String])>,
+) -> Result<()> {
+    exit_on_ctrl_c();
+
+    let mut fingerprint = None;
+    let mut last_render = Instant::now() - MIN_REFRESH_INTERVAL;
+    loop {
+        let current = get_fingerprint(git_dir, worktree);
+        if Some(current) != fingerprint && last_render.elapsed() >= MIN_REFRESH_INTERVAL {
+            let rendered = render()?;
+            print!("\x1b[2J\x1b[H{}", rendered);
+            io::stdout().flush().ok();
+            fingerprint = Some(current);
+            last_render = Instant::now();
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Exits with a success code, leaving the prompt on a new line, when Ctrl-C is pressed
+fn exit_on_ctrl_c() {
+    thread::spawn(|| {
+        let mut runtime = match tokio::runtime::Runtime::new() {
+            Ok(runtime) => runtime,
+            Err(_) => return,
+        };
+        if runtime.block_on(tokio::signal::ctrl_c()).is_ok() {
+            println!();
+            std::process::exit(0);
+        }
+    });
+}
+
+/// Hashes the paths, sizes and modification times of the watched files
+fn get_fingerprint(git_dir: &Path, worktree: Option<(&Path, &[String])>) -> u64 {
+    let mut files: Vec<PathBuf> = GIT_FILES.iter().map(|file| git_dir.join(file)).collect();
+    collect_files(&git_dir.join("refs"), &mut files);
+    if let Some((workdir, excluded)) = worktree {
+        let mut excluded = excluded.to_vec();
+        excluded.push(String::from(".git"));
+        if let Ok(worktree_files) = tracked_files::get_directory_files(workdir, &excluded) {
+            files.extend(worktree_files.into_iter().map(|file| workdir.join(file)));
+        }
+    }
+
+    let mut hasher = DefaultHasher::new();
+    for file in files {
+        let metadata = fs::metadata(&file).ok();
+        file.hash(&mut hasher);
+        metadata.as_ref().map(fs::Metadata::len).hash(&mut hasher);
+        metadata
+            .and_then(|metadata| metadata.modified().ok())
+            .hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}